@@ -61,6 +61,12 @@ impl Arg {
         }
     }
 
+    /// The switch without leading dashes or value, e.g. `user-agent`
+    pub(crate) fn name(&self) -> &str {
+        let key = self.key.trim_start_matches('-');
+        key.split('=').next().unwrap_or(key)
+    }
+
     pub fn values(
         key: impl AsRef<str>,
         values: impl IntoIterator<Item = impl fmt::Display>,
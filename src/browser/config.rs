@@ -5,11 +5,14 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use serde_json::{Map, Value};
+
 use super::argument::{Arg, ArgConst, ArgsBuilder};
 use crate::async_process::{self, Child, Stdio};
 use crate::detection::{self, DetectionOptions};
 use crate::handler::viewport::Viewport;
 use crate::handler::REQUEST_TIMEOUT;
+use crate::profiles::{ChaserProfile, WebRtcPolicy};
 
 /// Default `Browser::launch` timeout in MS
 pub const LAUNCH_TIMEOUT: u64 = 20_000;
@@ -97,6 +100,12 @@ pub struct BrowserConfig {
 
     /// Avoid easy bot detection by setting `navigator.webdriver` to false
     pub(crate) hidden: bool,
+
+    /// Which network interfaces WebRTC may gather candidates from
+    pub(crate) webrtc_policy: WebRtcPolicy,
+
+    /// The profile the browser is launched as
+    pub(crate) profile: Option<ChaserProfile>,
 }
 
 #[derive(Debug, Clone)]
@@ -116,12 +125,17 @@ pub struct BrowserConfigBuilder {
     ignore_invalid_events: bool,
     disable_https_first: bool,
     viewport: Option<Viewport>,
+    /// Whether `viewport` was set explicitly, which takes precedence over the
+    /// profile
+    viewport_set: bool,
     request_timeout: Duration,
     args: Vec<Arg>,
     disable_default_args: bool,
     request_intercept: bool,
     cache_enabled: bool,
     hidden: bool,
+    webrtc_policy: Option<WebRtcPolicy>,
+    profile: Option<ChaserProfile>,
 }
 
 impl BrowserConfig {
@@ -152,12 +166,15 @@ impl Default for BrowserConfigBuilder {
             ignore_invalid_events: true,
             disable_https_first: false,
             viewport: Some(Default::default()),
+            viewport_set: false,
             request_timeout: Duration::from_millis(REQUEST_TIMEOUT),
             args: Vec::new(),
             disable_default_args: false,
             request_intercept: false,
             cache_enabled: true,
             hidden: true,
+            webrtc_policy: None,
+            profile: None,
         }
     }
 }
@@ -227,6 +244,7 @@ impl BrowserConfigBuilder {
     /// option).
     pub fn viewport(mut self, viewport: impl Into<Option<Viewport>>) -> Self {
         self.viewport = viewport.into();
+        self.viewport_set = true;
         self
     }
 
@@ -331,7 +349,66 @@ impl BrowserConfigBuilder {
         self
     }
 
-    pub fn build(self) -> std::result::Result<BrowserConfig, String> {
+    /// Restrict the IP addresses WebRTC can leak. Pair it with the same
+    /// policy on the `ChaserProfile` so the page side matches.
+    pub fn webrtc_policy(mut self, policy: WebRtcPolicy) -> Self {
+        self.webrtc_policy = Some(policy);
+        self
+    }
+
+    /// Launch the browser as `profile`.
+    ///
+    /// Replaces the default `--user-agent` and `--lang` with the profile's,
+    /// writes its languages to the `Accept-Language` preference of the user
+    /// data dir, sizes the window to the profile's screen and takes over its
+    /// WebRTC policy. Service workers and requests sent before a page applied
+    /// its profile then agree with it. The viewport is only emulated for
+    /// mobile profiles, desktop pages fill the window.
+    ///
+    /// The profile also becomes the browser's default profile, see
+    /// [`Browser::set_default_profile`](crate::Browser::set_default_profile).
+    /// Explicit [`arg`](Self::arg)s for the same switches, a
+    /// [`window_size`](Self::window_size), [`viewport`](Self::viewport) or
+    /// [`webrtc_policy`](Self::webrtc_policy) take precedence regardless of
+    /// the order they are set in.
+    pub fn profile(mut self, profile: &ChaserProfile) -> Self {
+        self.profile = Some(profile.clone());
+        self
+    }
+
+    pub fn build(mut self) -> std::result::Result<BrowserConfig, String> {
+        if let Some(ref profile) = self.profile {
+            for arg in self
+                .args
+                .iter()
+                .filter(|arg| PROFILE_ARGS.contains(&arg.name()))
+            {
+                tracing::warn!(
+                    "--{} overrides the value derived from the browser profile",
+                    arg.name()
+                );
+            }
+
+            let screen = (profile.screen_width(), profile.screen_height());
+            match self.window_size {
+                Some(size) if size != screen => {
+                    tracing::warn!("window_size overrides the screen of the browser profile")
+                }
+                _ => self.window_size = Some(screen),
+            }
+            if self.viewport_set && (self.viewport.is_some() || profile.is_mobile()) {
+                tracing::warn!("viewport overrides the viewport derived from the browser profile");
+            } else {
+                self.viewport = profile.mobile_viewport();
+            }
+            match self.webrtc_policy {
+                Some(policy) if policy != profile.webrtc_policy() => tracing::warn!(
+                    "webrtc_policy overrides the WebRTC policy of the browser profile"
+                ),
+                _ => self.webrtc_policy = Some(profile.webrtc_policy()),
+            }
+        }
+
         let executable = if let Some(e) = self.executable {
             e
         } else {
@@ -359,6 +436,8 @@ impl BrowserConfigBuilder {
             request_intercept: self.request_intercept,
             cache_enabled: self.cache_enabled,
             hidden: self.hidden,
+            webrtc_policy: self.webrtc_policy.unwrap_or_default(),
+            profile: self.profile,
         })
     }
 }
@@ -370,7 +449,24 @@ impl BrowserConfig {
         if self.disable_default_args {
             builder.args(self.args.clone());
         } else {
-            builder.args(DEFAULT_ARGS.clone()).args(self.args.clone());
+            // the profile replaces the default user agent and language
+            let defaults = DEFAULT_ARGS
+                .iter()
+                .cloned()
+                .map(Arg::from)
+                .filter(|arg| self.profile.is_none() || !PROFILE_ARGS.contains(&arg.name()));
+            builder.args(defaults).args(self.args.clone());
+        }
+
+        if let Some(ref profile) = self.profile {
+            for arg in [
+                Arg::value("user-agent", profile.user_agent()),
+                Arg::value("lang", profile.locale()),
+            ] {
+                if !builder.has(arg.name()) {
+                    builder.arg(arg);
+                }
+            }
         }
 
         if !builder.has("remote-debugging-port") {
@@ -387,20 +483,23 @@ impl BrowserConfig {
             );
         }
 
-        if let Some(ref user_data) = self.user_data_dir {
-            builder.arg(Arg::value("user-data-dir", user_data.display()));
-        } else {
-            // If the user did not specify a data directory, this would default to the systems default
-            // data directory. In most cases, we would rather have a fresh instance of Chromium. Specify
-            // a temp dir just for chromiumoxide instead.
-            builder.arg(Arg::value(
-                "user-data-dir",
-                std::env::temp_dir().join("chromiumoxide-runner").display(),
-            ));
+        // If the user did not specify a data directory, this would default to the systems default
+        // data directory. In most cases, we would rather have a fresh instance of Chromium. Specify
+        // a temp dir just for chromiumoxide instead.
+        let user_data = self
+            .user_data_dir
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("chromiumoxide-runner"));
+        builder.arg(Arg::value("user-data-dir", user_data.display()));
+
+        if let Some(ref profile) = self.profile {
+            write_accept_languages(&user_data, &profile.languages().join(","))?;
         }
 
         if let Some((width, height)) = self.window_size {
-            builder.arg(Arg::values("window-size", [width, height]));
+            if !builder.has("window-size") {
+                builder.arg(Arg::values("window-size", [width, height]));
+            }
         }
 
         if !self.sandbox {
@@ -433,6 +532,10 @@ impl BrowserConfig {
             builder.arg(Arg::value("disable-blink-features", "AutomationControlled"));
         }
 
+        if let Some(policy) = self.webrtc_policy.ip_handling_policy() {
+            builder.arg(Arg::value("force-webrtc-ip-handling-policy", policy));
+        }
+
         if self.disable_https_first {
             builder.arg(Arg::values(
                 "disable-features",
@@ -452,6 +555,40 @@ impl BrowserConfig {
     }
 }
 
+/// Writes `languages` to the `intl.accept_languages` preference of the
+/// default profile in `user_data`, keeping all other preferences.
+///
+/// A `Preferences` file that isn't a JSON object is an error rather than
+/// being replaced.
+fn write_accept_languages(user_data: &Path, languages: &str) -> io::Result<()> {
+    let dir = user_data.join("Default");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("Preferences");
+
+    let mut prefs = match std::fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice::<Map<String, Value>>(&bytes).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid preferences {}: {err}", path.display()),
+            )
+        })?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Map::new(),
+        Err(err) => return Err(err),
+    };
+    let intl = prefs
+        .entry("intl")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !intl.is_object() {
+        *intl = Value::Object(Map::new());
+    }
+    intl["accept_languages"] = languages.into();
+
+    std::fs::write(&path, serde_json::to_vec(&prefs)?)
+}
+
+/// The switches [`BrowserConfigBuilder::profile`] derives from the profile
+static PROFILE_ARGS: [&str; 3] = ["user-agent", "lang", "window-size"];
+
 /// These are passed to the Chrome binary by default.
 /// Via https://github.com/puppeteer/puppeteer/blob/4846b8723cf20d3551c0d755df394cc5e0c82a94/src/node/Launcher.ts#L157
 static DEFAULT_ARGS: [ArgConst; 24] = [
@@ -483,3 +620,56 @@ static DEFAULT_ARGS: [ArgConst; 24] = [
     ArgConst::values("enable-blink-features", &["IdleDetection"]),
     ArgConst::values("lang", &["en_US"]),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_settings_win_over_the_profile() {
+        let profile = ChaserProfile::android().build();
+        let before = BrowserConfig::builder()
+            .chrome_executable("chrome")
+            .window_size(800, 600)
+            .viewport(None)
+            .profile(&profile)
+            .build()
+            .unwrap();
+        let after = BrowserConfig::builder()
+            .chrome_executable("chrome")
+            .profile(&profile)
+            .window_size(800, 600)
+            .viewport(None)
+            .build()
+            .unwrap();
+        for config in [before, after] {
+            assert_eq!(config.window_size, Some((800, 600)));
+            assert!(config.viewport.is_none());
+        }
+
+        let derived = BrowserConfig::builder()
+            .chrome_executable("chrome")
+            .profile(&profile)
+            .build()
+            .unwrap();
+        assert_eq!(derived.window_size, Some((412, 915)));
+        assert!(derived.viewport.unwrap().emulating_mobile);
+    }
+
+    #[test]
+    fn broken_preferences_are_kept() {
+        let user_data = std::env::temp_dir().join(format!("chaser-prefs-{}", std::process::id()));
+        let path = user_data.join("Default").join("Preferences");
+
+        write_accept_languages(&user_data, "de-DE,de").unwrap();
+        let prefs: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(prefs["intl"]["accept_languages"], "de-DE,de");
+
+        std::fs::write(&path, "{ not json").unwrap();
+        let err = write_accept_languages(&user_data, "en-US,en").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{ not json");
+
+        std::fs::remove_dir_all(&user_data).unwrap();
+    }
+}
@@ -1,5 +1,6 @@
 use std::future::Future;
 use std::io;
+use std::sync::Arc;
 
 use futures::channel::mpsc::{channel, unbounded, Sender};
 use futures::channel::oneshot::channel as oneshot_channel;
@@ -14,21 +15,24 @@ use chromiumoxide_cdp::cdp::browser_protocol::storage::{
     ClearCookiesParams, GetCookiesParams, SetCookiesParams,
 };
 use chromiumoxide_cdp::cdp::browser_protocol::target::{
-    CreateBrowserContextParams, CreateTargetParams, DisposeBrowserContextParams, TargetId,
-    TargetInfo,
+    CreateBrowserContextParams, CreateTargetParams, DisposeBrowserContextParams,
+    SetAutoAttachParams, TargetId, TargetInfo,
 };
 use chromiumoxide_cdp::cdp::{CdpEventMessage, IntoEventKind};
 use chromiumoxide_types::*;
 
 pub use self::config::{BrowserConfig, BrowserConfigBuilder, LAUNCH_TIMEOUT};
 use crate::async_process::{Child, ExitStatus};
+use crate::auth::Credentials;
 use crate::cmd::{to_command_response, CommandMessage};
 use crate::conn::Connection;
 use crate::error::{BrowserStderr, CdpError, Result};
 use crate::handler::browser::BrowserContext;
+use crate::handler::emulation::ProfileCommands;
 use crate::handler::{Handler, HandlerConfig, HandlerMessage};
 use crate::listeners::{EventListenerRequest, EventStream};
 use crate::page::Page;
+use crate::stealth::StealthProfile;
 use crate::utils;
 
 mod argument;
@@ -155,6 +159,14 @@ impl Browser {
         // Canonalize paths to reduce issues with sandboxing
         config.executable = utils::canonicalize_except_snap(config.executable).await?;
 
+        let default_profile = match config.profile {
+            Some(ref profile) => Some(Arc::new(ProfileCommands::new(
+                profile,
+                profile.bootstrap_script(),
+            )?)),
+            None => None,
+        };
+
         // Launch a new chromium instance
         let mut child = config.launch()?;
 
@@ -210,6 +222,7 @@ impl Browser {
             request_timeout: config.request_timeout,
             request_intercept: config.request_intercept,
             cache_enabled: config.cache_enabled,
+            default_profile,
         };
 
         let fut = Handler::new(conn, rx, handler_config);
@@ -447,6 +460,47 @@ impl Browser {
         rx.await?.ok_or(CdpError::NotFound)
     }
 
+    /// Applies `profile` to every page the browser opens from now on,
+    /// including popups and their out-of-process iframes and workers.
+    ///
+    /// New targets are paused at start until the profile has been applied, so
+    /// not even the first script of a page sees the real fingerprint. Popups
+    /// of a page with its own profile inherit that profile instead. Pages that
+    /// are already open are left as they are, see [`Page::apply_profile`].
+    pub async fn set_default_profile(&self, profile: &impl StealthProfile) -> Result<()> {
+        let commands = ProfileCommands::new(profile, profile.bootstrap_script())?;
+        self.sender
+            .clone()
+            .send(HandlerMessage::SetDefaultProfile(Arc::new(commands)))
+            .await?;
+        self.execute(
+            SetAutoAttachParams::builder()
+                .auto_attach(true)
+                .wait_for_debugger_on_start(true)
+                .flatten(true)
+                .build()
+                .map_err(CdpError::msg)?,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Answers auth challenges, e.g. of an authenticating proxy, with
+    /// `credentials` on every page the browser opens from now on, including
+    /// popups.
+    ///
+    /// Pages that are already open are left as they are, see
+    /// [`Page::authenticate`]. Requests of dedicated, shared and service
+    /// workers are not intercepted, so workers behind an authenticating proxy
+    /// still hit the challenge.
+    pub async fn authenticate(&self, credentials: Credentials) -> Result<()> {
+        self.sender
+            .clone()
+            .send(HandlerMessage::SetDefaultCredentials(credentials))
+            .await?;
+        Ok(())
+    }
+
     /// Set listener for browser event
     pub async fn event_listener<T: IntoEventKind>(&self) -> Result<EventStream<T>> {
         let (tx, rx) = unbounded();
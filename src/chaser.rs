@@ -1,5 +1,11 @@
+use crate::element::Element;
+use crate::evasions::EvasionSet;
+use crate::keys::{KeyStroke, KeyboardLayout, MODIFIER_ALT, MODIFIER_CTRL, MODIFIER_SHIFT};
+use crate::layout::ElementQuad;
+use crate::motion::{Bezier, MotionModel};
 use crate::page::Page;
-use crate::profiles::ChaserProfile;
+use crate::profiles::StealthProfile;
+use crate::typing::{TypingKey, TypingStyle};
 use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use chromiumoxide_cdp::cdp::browser_protocol::fetch::{
@@ -7,23 +13,31 @@ use chromiumoxide_cdp::cdp::browser_protocol::fetch::{
     FulfillRequestParams, HeaderEntry, RequestPattern,
 };
 use chromiumoxide_cdp::cdp::browser_protocol::input::{
-    DispatchKeyEventParams, DispatchKeyEventType,
+    DispatchKeyEventParams, DispatchKeyEventType, DispatchMouseEventParams, DispatchMouseEventType,
+    InsertTextParams, MouseButton,
 };
 use chromiumoxide_cdp::cdp::browser_protocol::network::ResourceType;
-use chromiumoxide_cdp::cdp::browser_protocol::page::{
-    AddScriptToEvaluateOnNewDocumentParams, CreateIsolatedWorldParams,
-};
+use chromiumoxide_cdp::cdp::browser_protocol::page::CreateIsolatedWorldParams;
 use chromiumoxide_cdp::cdp::js_protocol::runtime::EvaluateParams;
 use rand::Rng;
 use serde_json::Value;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Point {
     pub x: f64,
     pub y: f64,
 }
 
+/// The pointer position and the buttons held down
+#[derive(Debug, Clone, Copy)]
+struct MouseState {
+    pos: Point,
+    /// Bit field of the pressed buttons, as in `MouseEvent.buttons`
+    buttons: i64,
+}
+
 /// Stealth browser page with human-like input simulation.
 ///
 /// # Stealth JavaScript Execution
@@ -41,12 +55,19 @@ pub struct Point {
 /// # Features
 ///
 /// - Zero-footprint JS execution via `Page.createIsolatedWorld`
-/// - Bezier curve mouse movements with jitter
-/// - Realistic typing with variable delays
+/// - Mouse movements along a pluggable [`MotionModel`], Bezier curves by default
+/// - Realistic typing with variable delays and real key events of a
+///   [`KeyboardLayout`], following a [`TypingStyle`]
 #[derive(Clone, Debug)]
 pub struct ChaserPage {
     page: Page,
-    mouse_pos: Arc<Mutex<Point>>,
+    mouse: Arc<Mutex<MouseState>>,
+    motion: Arc<dyn MotionModel>,
+    /// The layout set with [`ChaserPage::with_keyboard_layout`]
+    keyboard_layout: Option<KeyboardLayout>,
+    /// The layout of the last applied profile
+    profile_keyboard_layout: Arc<Mutex<KeyboardLayout>>,
+    typing_style: TypingStyle,
 }
 
 impl ChaserPage {
@@ -54,10 +75,44 @@ impl ChaserPage {
     pub fn new(page: Page) -> Self {
         Self {
             page,
-            mouse_pos: Arc::new(Mutex::new(Point { x: 0.0, y: 0.0 })),
+            mouse: Arc::new(Mutex::new(MouseState {
+                pos: Point { x: 0.0, y: 0.0 },
+                buttons: 0,
+            })),
+            motion: Arc::new(Bezier),
+            keyboard_layout: None,
+            profile_keyboard_layout: Arc::new(Mutex::new(KeyboardLayout::Us)),
+            typing_style: TypingStyle::default(),
         }
     }
 
+    /// Type like `style` instead of [`TypingStyle::default`] in
+    /// [`Self::type_text_with_typos`], see [`crate::typing`]
+    pub fn with_typing_style(mut self, style: TypingStyle) -> Self {
+        self.typing_style = style;
+        self
+    }
+
+    /// Type with `layout` instead of the layout of the applied profile
+    pub fn with_keyboard_layout(mut self, layout: KeyboardLayout) -> Self {
+        self.keyboard_layout = Some(layout);
+        self
+    }
+
+    /// The layout typing goes through: the one set with
+    /// [`Self::with_keyboard_layout`], else the applied profile's, else US
+    pub fn keyboard_layout(&self) -> KeyboardLayout {
+        self.keyboard_layout
+            .unwrap_or_else(|| *self.profile_keyboard_layout.lock().unwrap())
+    }
+
+    /// Plan mouse movements with `model` instead of [`Bezier`], see
+    /// [`crate::motion`]
+    pub fn with_motion_model(mut self, model: impl MotionModel + 'static) -> Self {
+        self.motion = Arc::new(model);
+        self
+    }
+
     // ========== SAFE PAGE ACCESS ==========
 
     /// Access the underlying Page.
@@ -115,11 +170,16 @@ impl ChaserPage {
         self.evaluate_stealth(script).await
     }
 
-    /// Apply a ChaserProfile to this page in one clean call.
+    /// Apply a profile to this page in one clean call.
     ///
-    /// This method:
-    /// 1. Sets the User-Agent HTTP header
-    /// 2. Injects the profile's bootstrap script for JS-level spoofing
+    /// Accepts a [`ChaserProfile`](crate::profiles::ChaserProfile) or any other
+    /// [`StealthProfile`]. This method:
+    /// 1. Sets the User-Agent, client hints (`Sec-CH-UA*`) and `Accept-Language`
+    ///    HTTP headers
+    /// 2. Emulates the profile's timezone, locale and screen size, and for
+    ///    mobile profiles the device viewport and touch screen
+    /// 3. Injects the profile's bootstrap script for JS-level spoofing
+    /// 4. Types with the profile's [`KeyboardLayout`] from now on
     ///
     /// **IMPORTANT:** Call this BEFORE navigating to the target site.
     ///
@@ -131,25 +191,32 @@ impl ChaserPage {
     /// chaser.apply_profile(&profile).await?;
     /// chaser.inner().goto("https://example.com").await?;
     /// ```
-    pub async fn apply_profile(&self, profile: &ChaserProfile) -> Result<()> {
-        // 1. Set the HTTP User-Agent header
+    pub async fn apply_profile(&self, profile: &impl StealthProfile) -> Result<()> {
+        *self.profile_keyboard_layout.lock().unwrap() = profile.keyboard_layout();
         self.page
-            .set_user_agent(&profile.user_agent())
+            .apply_profile(profile)
             .await
-            .map_err(|e| anyhow!("{}", e))?;
+            .map_err(|e| anyhow!("{}", e))
+    }
 
-        // 2. Inject the bootstrap script to run on every new document
+    /// Apply a profile with a custom set of evasion modules, e.g. to turn off
+    /// a single patch that breaks a site.
+    ///
+    /// # Example
+    /// ```rust
+    /// let evasions = EvasionSet::builder().disable("stack_trace").build();
+    /// chaser.apply_profile_with_evasions(&profile, &evasions).await?;
+    /// ```
+    pub async fn apply_profile_with_evasions(
+        &self,
+        profile: &impl StealthProfile,
+        evasions: &EvasionSet,
+    ) -> Result<()> {
+        *self.profile_keyboard_layout.lock().unwrap() = profile.keyboard_layout();
         self.page
-            .execute(AddScriptToEvaluateOnNewDocumentParams {
-                source: profile.bootstrap_script(),
-                world_name: None,
-                include_command_line_api: None,
-                run_immediately: None,
-            })
+            .apply_profile_with_evasions(profile, evasions)
             .await
-            .map_err(|e| anyhow!("{}", e))?;
-
-        Ok(())
+            .map_err(|e| anyhow!("{}", e))
     }
 
     // ========== REQUEST INTERCEPTION API ==========
@@ -326,55 +393,125 @@ impl ChaserPage {
         Ok(res.result.result.value)
     }
 
-    /// Moves the mouse to the target coordinates using a human-like Bezier curve path.
+    /// Moves the mouse to the target coordinates along a human-like path.
     ///
-    /// The path includes:
+    /// The path and its timing come from the page's [`MotionModel`], by
+    /// default a [`Bezier`] curve with:
     /// - Randomized control points for natural arcs
     /// - 20% chance of slight overshoot
-    /// - Target jitter (±2px)
     /// - Variable delays between movements (5-15ms)
+    ///
+    /// The target gets a jitter of ±2px and counts as a
+    /// [`DEFAULT_TARGET_WIDTH`] wide target for distance-dependent models.
     pub async fn move_mouse_human(&self, x: f64, y: f64) -> Result<()> {
-        let start = { *self.mouse_pos.lock().unwrap() };
-        let end = Point { x, y };
-
         let mut rng = rand::thread_rng();
 
         // Target Selection Jitter: don't land exactly on the pixel
         let jitter_x = rng.gen_range(-2.0..2.0);
         let jitter_y = rng.gen_range(-2.0..2.0);
-        let target_with_jitter = Point {
-            x: end.x + jitter_x,
-            y: end.y + jitter_y,
-        };
-
-        let path = BezierPath::generate(start, target_with_jitter, 25);
+        self.move_mouse_along_path(
+            Point {
+                x: x + jitter_x,
+                y: y + jitter_y,
+            },
+            DEFAULT_TARGET_WIDTH,
+        )
+        .await
+    }
 
-        for point in path {
+    /// Follows the motion model's path from the current mouse position to
+    /// exactly `end`, holding the pressed buttons
+    async fn move_mouse_along_path(&self, end: Point, target_width: f64) -> Result<()> {
+        let start = { self.mouse.lock().unwrap().pos };
+
+        for step in self.motion.plan(start, end, target_width) {
+            let state = { *self.mouse.lock().unwrap() };
+            let mut params = DispatchMouseEventParams::new(
+                DispatchMouseEventType::MouseMoved,
+                step.point.x,
+                step.point.y,
+            );
+            if state.buttons != 0 {
+                params.button = Some(held_button(state.buttons));
+                params.buttons = Some(state.buttons);
+            }
             self.page
-                .move_mouse(crate::layout::Point {
-                    x: point.x,
-                    y: point.y,
-                })
+                .execute(params)
                 .await
                 .map_err(|e| anyhow!("{}", e))?;
-            *self.mouse_pos.lock().unwrap() = point;
-            // Tiny delay to simulate physical movement
-            tokio::time::sleep(tokio::time::Duration::from_millis(rng.gen_range(5..15))).await;
+            self.mouse.lock().unwrap().pos = step.point;
+            tokio::time::sleep(step.delay).await;
         }
 
         Ok(())
     }
 
-    /// Perform a click at the current mouse position.
-    pub async fn click(&self) -> Result<()> {
-        let pos = { *self.mouse_pos.lock().unwrap() };
+    /// Press `button` at the current mouse position and keep it held until
+    /// [`Self::mouse_up`]. Moves in between carry the button, like a drag.
+    pub async fn mouse_down(&self, button: MouseButton) -> Result<()> {
+        self.dispatch_button(DispatchMouseEventType::MousePressed, button, 1)
+            .await
+    }
+
+    /// Release `button` at the current mouse position
+    pub async fn mouse_up(&self, button: MouseButton) -> Result<()> {
+        self.dispatch_button(DispatchMouseEventType::MouseReleased, button, 1)
+            .await
+    }
+
+    /// Sends a press or release of `button` and updates the held buttons
+    async fn dispatch_button(
+        &self,
+        r#type: DispatchMouseEventType,
+        button: MouseButton,
+        click_count: i64,
+    ) -> Result<()> {
+        let pressed = r#type == DispatchMouseEventType::MousePressed;
+        let state = {
+            let mut state = self.mouse.lock().unwrap();
+            if pressed {
+                state.buttons |= button_mask(&button);
+            } else {
+                state.buttons &= !button_mask(&button);
+            }
+            *state
+        };
+        let params = DispatchMouseEventParams::builder()
+            .r#type(r#type)
+            .x(state.pos.x)
+            .y(state.pos.y)
+            .button(button)
+            .buttons(state.buttons)
+            .click_count(click_count)
+            .build()
+            .unwrap();
         self.page
-            .click(crate::layout::Point { x: pos.x, y: pos.y })
+            .execute(params)
             .await
             .map_err(|e| anyhow!("{}", e))?;
         Ok(())
     }
 
+    /// Press and release `button` with a randomized hold time
+    async fn press_button(&self, button: MouseButton, click_count: i64) -> Result<()> {
+        self.dispatch_button(
+            DispatchMouseEventType::MousePressed,
+            button.clone(),
+            click_count,
+        )
+        .await?;
+        let hold = rand::thread_rng().gen_range(60..140);
+        tokio::time::sleep(tokio::time::Duration::from_millis(hold)).await;
+        self.dispatch_button(DispatchMouseEventType::MouseReleased, button, click_count)
+            .await
+    }
+
+    /// Perform a left click at the current mouse position, holding the button
+    /// for 60-140ms.
+    pub async fn click(&self) -> Result<()> {
+        self.press_button(MouseButton::Left, 1).await
+    }
+
     /// Move to target and click with full human-like behavior.
     ///
     /// Combines Bezier curve mouse movement with a natural click, including:
@@ -382,38 +519,194 @@ impl ChaserPage {
     /// - Small random delay before clicking (50-150ms)
     /// - Variable click duration
     pub async fn click_human(&self, x: f64, y: f64) -> Result<()> {
-        let mut rng = rand::thread_rng();
-
         // Move to target with bezier curve
         self.move_mouse_human(x, y).await?;
+        self.click_after_arrival().await
+    }
 
+    /// Clicks at the current position with the pauses around it a human takes
+    async fn click_after_arrival(&self) -> Result<()> {
         // Small pause before clicking (humans don't click instantly after arriving)
-        tokio::time::sleep(tokio::time::Duration::from_millis(rng.gen_range(50..150))).await;
+        let delay = rand::thread_rng().gen_range(50..150);
+        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
 
         // Click
         self.click().await?;
 
         // Small pause after clicking
-        tokio::time::sleep(tokio::time::Duration::from_millis(rng.gen_range(30..80))).await;
+        let delay = rand::thread_rng().gen_range(30..80);
+        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
 
         Ok(())
     }
 
-    /// Type text with human-like delays between keystrokes.
+    /// Move to target and double click it like [`Self::click_human`], the
+    /// second click follows within 60-140ms.
+    pub async fn double_click_human(&self, x: f64, y: f64) -> Result<()> {
+        self.move_mouse_human(x, y).await?;
+        let delay = rand::thread_rng().gen_range(50..150);
+        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+
+        self.press_button(MouseButton::Left, 1).await?;
+        let gap = rand::thread_rng().gen_range(60..140);
+        tokio::time::sleep(tokio::time::Duration::from_millis(gap)).await;
+        self.press_button(MouseButton::Left, 2).await?;
+
+        let delay = rand::thread_rng().gen_range(30..80);
+        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+        Ok(())
+    }
+
+    /// Move to target and right click it like [`Self::click_human`]
+    pub async fn right_click_human(&self, x: f64, y: f64) -> Result<()> {
+        self.move_mouse_human(x, y).await?;
+        let delay = rand::thread_rng().gen_range(50..150);
+        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+
+        self.press_button(MouseButton::Right, 1).await?;
+
+        let delay = rand::thread_rng().gen_range(30..80);
+        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+        Ok(())
+    }
+
+    /// Drag with the left button from `from` to exactly `to`, e.g. a slider
+    /// handle or a list item.
+    ///
+    /// Moves to `from` like [`Self::move_mouse_human`], holds the button a
+    /// moment before moving along the motion model's path and rests on `to`
+    /// before releasing.
+    pub async fn drag_human(&self, from: Point, to: Point) -> Result<()> {
+        self.move_mouse_human(from.x, from.y).await?;
+        let delay = rand::thread_rng().gen_range(50..150);
+        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+
+        self.mouse_down(MouseButton::Left).await?;
+        let grab = rand::thread_rng().gen_range(80..200);
+        tokio::time::sleep(tokio::time::Duration::from_millis(grab)).await;
+
+        self.move_mouse_along_path(to, DEFAULT_TARGET_WIDTH).await?;
+        let rest = rand::thread_rng().gen_range(60..180);
+        tokio::time::sleep(tokio::time::Duration::from_millis(rest)).await;
+
+        self.mouse_up(MouseButton::Left).await
+    }
+
+    /// Move the mouse onto `element` like [`Self::move_mouse_human`].
     ///
-    /// Simulates realistic typing with:
-    /// - Variable delay between keys (50-150ms by default)
-    /// - Occasional longer pauses (5% chance of 200-400ms pause)
+    /// Scrolls the element into view with [`Self::scroll_human`] first and
+    /// lands on a random point inside it, biased towards the center and away
+    /// from the edges.
+    pub async fn hover_element_human(&self, element: &Element) -> Result<()> {
+        let quad = self.scroll_into_view_human(element).await?;
+        let target = point_in_quad(&quad, &mut rand::thread_rng());
+        self.move_mouse_along_path(target, quad.width().min(quad.height()))
+            .await
+    }
+
+    /// Scroll `element` into view, move onto it and click it like
+    /// [`Self::click_human`], see [`Self::hover_element_human`].
+    pub async fn click_element_human(&self, element: &Element) -> Result<()> {
+        self.hover_element_human(element).await?;
+        self.click_after_arrival().await
+    }
+
+    /// Click into `element` with [`Self::click_element_human`] and type `text`
+    /// with [`Self::type_text`].
+    pub async fn type_into_human(&self, element: &Element, text: &str) -> Result<()> {
+        self.click_element_human(element).await?;
+        // a moment to see the caret before typing
+        let delay = rand::thread_rng().gen_range(100..300);
+        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+        self.type_text(text).await
+    }
+
+    /// Scrolls with the wheel until the first quad of `element` is inside the
+    /// viewport and returns it. Falls back to a jump scroll if wheel scrolling
+    /// doesn't get there, e.g. inside a scroll container the mouse isn't over,
+    /// or if the element is out of view horizontally.
+    async fn scroll_into_view_human(&self, element: &Element) -> Result<ElementQuad> {
+        let viewport = self
+            .page
+            .layout_metrics()
+            .await
+            .map_err(|e| anyhow!("{}", e))?
+            .css_layout_viewport;
+        let width = viewport.client_width as f64;
+        let height = viewport.client_height as f64;
+
+        for _ in 0..MAX_SCROLL_ATTEMPTS {
+            let quad = first_quad(element).await?;
+            let center = quad.quad_center();
+            // an element larger than the viewport is in view once its center is
+            let in_view_horizontally = if quad.width() > width {
+                (0.0..width).contains(&center.x)
+            } else {
+                quad.most_left() >= 0.0 && quad.most_right() <= width
+            };
+            if !in_view_horizontally {
+                // the wheel only scrolls vertically
+                break;
+            }
+            let in_view = if quad.height() > height {
+                (0.0..height).contains(&center.y)
+            } else {
+                quad.most_top() >= 0.0 && quad.most_bottom() <= height
+            };
+            if in_view {
+                return Ok(quad);
+            }
+
+            // aim for roughly the middle of the viewport, never exactly
+            let offset = rand::thread_rng().gen_range(-0.1..0.1) * height;
+            self.scroll_human((center.y - height / 2.0 + offset) as i32)
+                .await?;
+            let delay = rand::thread_rng().gen_range(100..250);
+            tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+        }
+
+        element
+            .scroll_into_view()
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+        first_quad(element).await
+    }
+
+    /// Type text with human-like delays between keystrokes, 50-150ms apart.
+    ///
+    /// See [`Self::type_text_with_typos`] and [`Self::type_text_with_style`]
+    /// to type like a [`TypingStyle`].
     pub async fn type_text(&self, text: &str) -> Result<()> {
         self.type_text_with_delay(text, 50, 150).await
     }
 
+    /// Type text like `style`, with its speed, key hold times, bursts and
+    /// corrected typos.
+    pub async fn type_text_with_style(&self, text: &str, style: &TypingStyle) -> Result<()> {
+        for step in style.plan(text, self.keyboard_layout()) {
+            tokio::time::sleep(step.delay).await;
+            match step.key {
+                TypingKey::Char(c) => self.type_char(c, step.dwell).await?,
+                TypingKey::Backspace => {
+                    let stroke = self
+                        .keyboard_layout()
+                        .key("Backspace")
+                        .ok_or_else(|| anyhow!("Key not found: Backspace"))?;
+                    self.press_stroke(&stroke, step.dwell).await?
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Type text with custom delay range (in milliseconds).
     ///
     /// # Arguments
     /// * `text` - The text to type
     /// * `min_delay_ms` - Minimum delay between keystrokes
     /// * `max_delay_ms` - Maximum delay between keystrokes
+    ///
+    /// Keys are tapped without being held, the delays are the only pauses.
     pub async fn type_text_with_delay(
         &self,
         text: &str,
@@ -423,28 +716,7 @@ impl ChaserPage {
         let mut rng = rand::thread_rng();
 
         for c in text.chars() {
-            // Send keyDown with the character
-            let key_down = DispatchKeyEventParams::builder()
-                .r#type(DispatchKeyEventType::KeyDown)
-                .text(c.to_string())
-                .build()
-                .unwrap();
-
-            self.page
-                .execute(key_down)
-                .await
-                .map_err(|e| anyhow!("{}", e))?;
-
-            // Send keyUp
-            let key_up = DispatchKeyEventParams::builder()
-                .r#type(DispatchKeyEventType::KeyUp)
-                .build()
-                .unwrap();
-
-            self.page
-                .execute(key_up)
-                .await
-                .map_err(|e| anyhow!("{}", e))?;
+            self.type_char(c, Duration::ZERO).await?;
 
             // Random delay between keystrokes
             let delay = rng.gen_range(min_delay_ms..max_delay_ms);
@@ -462,47 +734,14 @@ impl ChaserPage {
         Ok(())
     }
 
-    /// Press a specific key (e.g., "Enter", "Tab", "Escape").
+    /// Press a specific key (e.g., "Enter", "Tab", "Escape") or type a single
+    /// character, see [`KeyboardLayout::key`].
     pub async fn press_key(&self, key: &str) -> Result<()> {
-        // Map common key names to their key codes
-        let (key_str, code) = match key {
-            "Enter" => ("Enter", "Enter"),
-            "Tab" => ("Tab", "Tab"),
-            "Escape" => ("Escape", "Escape"),
-            "Backspace" => ("Backspace", "Backspace"),
-            "Delete" => ("Delete", "Delete"),
-            "ArrowUp" => ("ArrowUp", "ArrowUp"),
-            "ArrowDown" => ("ArrowDown", "ArrowDown"),
-            "ArrowLeft" => ("ArrowLeft", "ArrowLeft"),
-            "ArrowRight" => ("ArrowRight", "ArrowRight"),
-            _ => (key, key),
-        };
-
-        let key_down = DispatchKeyEventParams::builder()
-            .r#type(DispatchKeyEventType::RawKeyDown)
-            .key(key_str)
-            .code(code)
-            .build()
-            .unwrap();
-
-        self.page
-            .execute(key_down)
-            .await
-            .map_err(|e| anyhow!("{}", e))?;
-
-        let key_up = DispatchKeyEventParams::builder()
-            .r#type(DispatchKeyEventType::KeyUp)
-            .key(key_str)
-            .code(code)
-            .build()
-            .unwrap();
-
-        self.page
-            .execute(key_up)
-            .await
-            .map_err(|e| anyhow!("{}", e))?;
-
-        Ok(())
+        let stroke = self
+            .keyboard_layout()
+            .key(key)
+            .ok_or_else(|| anyhow!("Key not found: {key}"))?;
+        self.press_stroke(&stroke, random_dwell()).await
     }
 
     /// Press Enter key with a small random delay before pressing.
@@ -529,12 +768,8 @@ impl ChaserPage {
     /// # Arguments
     /// * `delta_y` - Total pixels to scroll (positive = down, negative = up)
     pub async fn scroll_human(&self, delta_y: i32) -> Result<()> {
-        use chromiumoxide_cdp::cdp::browser_protocol::input::{
-            DispatchMouseEventParams, DispatchMouseEventType, MouseButton,
-        };
-
         let mut rng = rand::thread_rng();
-        let pos = { *self.mouse_pos.lock().unwrap() };
+        let pos = { self.mouse.lock().unwrap().pos };
 
         // Number of scroll steps (more steps = smoother)
         let steps = (delta_y.abs() / 50).clamp(3, 15) as usize;
@@ -584,67 +819,163 @@ impl ChaserPage {
 
     /// Type text with occasional typos and corrections for ultra-realistic input.
     ///
-    /// This method has a small chance (~3%) of making a typo and then correcting it,
-    /// mimicking how real humans type.
+    /// Follows the page's [`TypingStyle`]: by default about 3% of letters and
+    /// digits get mistyped on a neighboring key, swapped with the next one or
+    /// pressed twice, then deleted and retyped once noticed.
     pub async fn type_text_with_typos(&self, text: &str) -> Result<()> {
-        let mut rng = rand::thread_rng();
-        let typo_chars = ['q', 'w', 'e', 'r', 't', 'a', 's', 'd', 'f', 'g'];
+        self.type_text_with_style(text, &self.typing_style).await
+    }
 
-        for c in text.chars() {
-            // 3% chance of typo
-            if rng.gen_bool(0.03) && c.is_alphabetic() {
-                // Type wrong character
-                let typo = typo_chars[rng.gen_range(0..typo_chars.len())];
-                self.type_single_char(typo).await?;
-
-                // Brief pause to "notice" the mistake
-                tokio::time::sleep(tokio::time::Duration::from_millis(rng.gen_range(100..300)))
-                    .await;
-
-                // Backspace to correct
-                self.press_key("Backspace").await?;
-                tokio::time::sleep(tokio::time::Duration::from_millis(rng.gen_range(30..80))).await;
+    /// Types `c` with the keys of the keyboard layout. Characters the layout
+    /// has no key for are inserted like an IME would.
+    async fn type_char(&self, c: char, dwell: Duration) -> Result<()> {
+        match self.keyboard_layout().key_stroke(c) {
+            Some(stroke) => self.press_stroke(&stroke, dwell).await,
+            None => {
+                self.page
+                    .execute(InsertTextParams::new(c.to_string()))
+                    .await
+                    .map_err(|e| anyhow!("{}", e))?;
+                Ok(())
             }
-
-            // Type the correct character
-            self.type_single_char(c).await?;
-
-            // Random delay
-            let delay = rng.gen_range(50..150);
-            let actual_delay = if rng.gen_bool(0.05) {
-                rng.gen_range(200..400) // thinking pause
-            } else {
-                delay
-            };
-            tokio::time::sleep(tokio::time::Duration::from_millis(actual_delay)).await;
         }
+    }
 
+    /// Presses `stroke` for `dwell`, holding Shift or AltGr around it.
+    ///
+    /// A zero `dwell` taps the key and its modifiers without any pause.
+    async fn press_stroke(&self, stroke: &KeyStroke, dwell: Duration) -> Result<()> {
+        let mut modifiers = if stroke.shift { MODIFIER_SHIFT } else { 0 };
+        if stroke.alt_graph {
+            // Chrome on Windows reports AltGr as Ctrl+Alt
+            modifiers |= MODIFIER_ALT | MODIFIER_CTRL;
+        }
+        let modifier_keys = [
+            (stroke.shift, "Shift", "ShiftLeft", 1),
+            (stroke.alt_graph, "AltGraph", "AltRight", 2),
+        ];
+        let modifier_keys = modifier_keys
+            .into_iter()
+            .filter(|(held, ..)| *held)
+            .filter_map(|(_, name, code, location)| {
+                let stroke = KeyboardLayout::Us.key(name)?;
+                Some((KeyStroke { code, ..stroke }, location))
+            })
+            .collect::<Vec<_>>();
+
+        for (key, location) in &modifier_keys {
+            self.dispatch_key(DispatchKeyEventType::RawKeyDown, key, modifiers, *location)
+                .await?;
+            if !dwell.is_zero() {
+                let lead = rand::thread_rng().gen_range(20..60);
+                tokio::time::sleep(Duration::from_millis(lead)).await;
+            }
+        }
+        let key_down = if stroke.text.is_some() {
+            DispatchKeyEventType::KeyDown
+        } else {
+            DispatchKeyEventType::RawKeyDown
+        };
+        self.dispatch_key(key_down, stroke, modifiers, 0).await?;
+        if !dwell.is_zero() {
+            tokio::time::sleep(dwell).await;
+        }
+        self.dispatch_key(DispatchKeyEventType::KeyUp, stroke, modifiers, 0)
+            .await?;
+        for (key, location) in modifier_keys.iter().rev() {
+            if !dwell.is_zero() {
+                let lag = rand::thread_rng().gen_range(10..40);
+                tokio::time::sleep(Duration::from_millis(lag)).await;
+            }
+            self.dispatch_key(DispatchKeyEventType::KeyUp, key, 0, *location)
+                .await?;
+        }
         Ok(())
     }
 
-    /// Helper to type a single character
-    async fn type_single_char(&self, c: char) -> Result<()> {
-        let key_down = DispatchKeyEventParams::builder()
-            .r#type(DispatchKeyEventType::KeyDown)
-            .text(c.to_string())
-            .build()
-            .unwrap();
-
+    async fn dispatch_key(
+        &self,
+        r#type: DispatchKeyEventType,
+        stroke: &KeyStroke,
+        modifiers: i64,
+        location: i64,
+    ) -> Result<()> {
+        let mut params = DispatchKeyEventParams::builder()
+            .r#type(r#type.clone())
+            .key(stroke.key)
+            .code(stroke.code)
+            .windows_virtual_key_code(stroke.key_code)
+            .native_virtual_key_code(stroke.key_code)
+            .modifiers(modifiers)
+            .location(location);
+        if let (DispatchKeyEventType::KeyDown, Some(text)) = (r#type, stroke.text) {
+            params = params.text(text).unmodified_text(text);
+        }
         self.page
-            .execute(key_down)
+            .execute(params.build().unwrap())
             .await
             .map_err(|e| anyhow!("{}", e))?;
+        Ok(())
+    }
+}
 
-        let key_up = DispatchKeyEventParams::builder()
-            .r#type(DispatchKeyEventType::KeyUp)
-            .build()
-            .unwrap();
+/// A key hold time for presses outside of a [`TypingStyle`]
+fn random_dwell() -> Duration {
+    Duration::from_millis(rand::thread_rng().gen_range(50..110))
+}
 
-        self.page
-            .execute(key_up)
-            .await
-            .map_err(|e| anyhow!("{}", e))?;
-        Ok(())
+/// The bit of `button` in `MouseEvent.buttons`
+fn button_mask(button: &MouseButton) -> i64 {
+    match button {
+        MouseButton::None => 0,
+        MouseButton::Left => 1,
+        MouseButton::Right => 2,
+        MouseButton::Middle => 4,
+        MouseButton::Back => 8,
+        MouseButton::Forward => 16,
+    }
+}
+
+/// The button moves report while `buttons` are held
+fn held_button(buttons: i64) -> MouseButton {
+    [
+        MouseButton::Left,
+        MouseButton::Right,
+        MouseButton::Middle,
+        MouseButton::Back,
+        MouseButton::Forward,
+    ]
+    .into_iter()
+    .find(|button| buttons & button_mask(button) != 0)
+    .unwrap_or(MouseButton::None)
+}
+
+/// The target size of [`ChaserPage::move_mouse_human`], roughly a button
+pub const DEFAULT_TARGET_WIDTH: f64 = 24.0;
+
+/// How often [`ChaserPage::scroll_into_view_human`] scrolls before jumping
+const MAX_SCROLL_ATTEMPTS: usize = 10;
+
+async fn first_quad(element: &Element) -> Result<ElementQuad> {
+    element
+        .content_quads()
+        .await
+        .map_err(|e| anyhow!("{}", e))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("Node is either not visible or not an HTMLElement"))
+}
+
+/// A random point inside `quad`. The relative position on each axis averages
+/// three uniform samples, which concentrates the points around the center,
+/// and keeps 15% of the size clear of every edge.
+fn point_in_quad(quad: &ElementQuad, rng: &mut impl Rng) -> Point {
+    let mut relative = || 0.15 + 0.7 * (0..3).map(|_| rng.gen::<f64>()).sum::<f64>() / 3.0;
+    let (u, v) = (relative(), relative());
+    let point = quad.point_at(u, v);
+    Point {
+        x: point.x,
+        y: point.y,
     }
 }
 
@@ -711,3 +1042,32 @@ impl BezierPath {
         path
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout;
+
+    #[test]
+    fn points_in_quads_stay_clear_of_the_edges() {
+        let quad = ElementQuad {
+            top_left: layout::Point::new(100.0, 50.0),
+            top_right: layout::Point::new(300.0, 50.0),
+            bottom_right: layout::Point::new(300.0, 90.0),
+            bottom_left: layout::Point::new(100.0, 90.0),
+        };
+        let mut rng = rand::thread_rng();
+        let points = (0..1000)
+            .map(|_| point_in_quad(&quad, &mut rng))
+            .collect::<Vec<_>>();
+        assert!(points
+            .iter()
+            .all(|p| (130.0..=270.0).contains(&p.x) && (56.0..=84.0).contains(&p.y)));
+        assert!(points.iter().any(|p| p.x != 200.0 || p.y != 70.0));
+        let central = points
+            .iter()
+            .filter(|p| (165.0..235.0).contains(&p.x))
+            .count();
+        assert!(central > 600, "{central}");
+    }
+}
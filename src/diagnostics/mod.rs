@@ -0,0 +1,241 @@
+//! Offline fingerprint audit.
+//!
+//! A set of detection probes is served from a local [`ProbeServer`] and run
+//! in the page, and every observation is compared with what the profile is
+//! supposed to report. Nothing leaves the machine, so the audit can gate
+//! profile changes in CI:
+//!
+//! ```rust,no_run
+//! use chaser_oxide::diagnostics::run_fingerprint_audit;
+//! use chaser_oxide::{Browser, BrowserConfig, ChaserPage, ChaserProfile};
+//! use futures::StreamExt;
+//!
+//! # async fn audit() -> anyhow::Result<()> {
+//! let (browser, mut handler) =
+//!     Browser::launch(BrowserConfig::builder().build().map_err(anyhow::Error::msg)?).await?;
+//! tokio::spawn(async move { while handler.next().await.is_some() {} });
+//!
+//! let profile = ChaserProfile::windows().build();
+//! let chaser = ChaserPage::new(browser.new_page("about:blank").await?);
+//! chaser.apply_profile(&profile).await?;
+//!
+//! let report = run_fingerprint_audit(&chaser, &profile).await?;
+//! println!("{report}");
+//! assert!(report.passed());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::chaser::ChaserPage;
+use crate::stealth::StealthProfile;
+
+mod probes;
+mod server;
+
+pub use server::ProbeServer;
+
+/// How long the probes may take before the audit gives up
+const AUDIT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The outcome of a single probe
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCheck {
+    /// The name of the probe, e.g. `webdriver`
+    pub name: String,
+    /// What the page observed
+    pub observed: String,
+    /// What the profile should make the page observe
+    pub expected: String,
+    /// Whether `observed` matches `expected`
+    pub passed: bool,
+}
+
+/// The result of [`run_fingerprint_audit`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditReport {
+    /// All checks in the order they were run
+    pub checks: Vec<AuditCheck>,
+}
+
+impl AuditReport {
+    /// Whether every check passed
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// The checks that failed
+    pub fn failures(&self) -> impl Iterator<Item = &AuditCheck> + '_ {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+
+    /// Returns the check `name`
+    pub fn check(&self, name: &str) -> Option<&AuditCheck> {
+        self.checks.iter().find(|check| check.name == name)
+    }
+
+    /// Compares the observations of the probe page with `expected`
+    fn new(observed: &Value, expected: Vec<(&str, String)>) -> Self {
+        let checks = expected
+            .into_iter()
+            .map(|(name, expected)| {
+                let observed = observed
+                    .get(name)
+                    .and_then(Value::as_str)
+                    .unwrap_or("missing")
+                    .to_string();
+                AuditCheck {
+                    name: name.to_string(),
+                    passed: observed == expected,
+                    observed,
+                    expected,
+                }
+            })
+            .collect();
+        Self { checks }
+    }
+}
+
+impl fmt::Display for AuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            if check.passed {
+                writeln!(f, "PASS {}: {}", check.name, check.observed)?;
+            } else {
+                writeln!(
+                    f,
+                    "FAIL {}: observed {:?}, expected {:?}",
+                    check.name, check.observed, check.expected
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs the detection probes in `page` and compares them with `profile`.
+///
+/// The probes cover `navigator.webdriver`, the CDP `Runtime` leak,
+/// `Function.prototype.toString` of patched functions, iframe and worker
+/// consistency, the User-Agent against the client hints, WebGL, plugins and
+/// permissions. Apply the profile to the page before running the audit, the
+/// page navigates away from its current url.
+pub async fn run_fingerprint_audit(
+    page: &ChaserPage,
+    profile: &impl StealthProfile,
+) -> Result<AuditReport> {
+    let server = ProbeServer::start()?;
+    page.goto(&server.url("/")).await?;
+
+    let started = Instant::now();
+    let observed = loop {
+        let audit = page
+            .evaluate("document.documentElement.dataset.audit || null")
+            .await?;
+        if let Some(Value::String(json)) = audit {
+            break serde_json::from_str::<Value>(&json)?;
+        }
+        if started.elapsed() > AUDIT_TIMEOUT {
+            return Err(anyhow!("The audit probes did not finish in time"));
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+
+    Ok(AuditReport::new(&observed, expectations(profile)))
+}
+
+/// What every probe should observe for `profile`
+fn expectations(profile: &impl StealthProfile) -> Vec<(&'static str, String)> {
+    let metadata = profile.user_agent_metadata();
+    let brands = metadata
+        .as_ref()
+        .and_then(|m| m.brands.as_ref())
+        .map(|brands| {
+            brands
+                .iter()
+                .map(|b| format!("{} {}", b.brand, b.version))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_else(|| "none".to_string());
+    let hints_platform = metadata
+        .as_ref()
+        .map(|m| m.platform.clone())
+        .unwrap_or_else(|| "none".to_string());
+    // desktop Chrome always ships the built-in PDF viewer
+    let plugins = if profile.mobile_viewport().is_some() {
+        "0 plugins, 0 mime types"
+    } else {
+        "5 plugins, 2 mime types"
+    };
+
+    vec![
+        ("webdriver", "false".to_string()),
+        ("user_agent", profile.user_agent().to_string()),
+        ("platform", profile.platform().to_string()),
+        (
+            "hardware_concurrency",
+            profile.hardware_concurrency().to_string(),
+        ),
+        ("device_memory", profile.device_memory().to_string()),
+        ("languages", profile.languages().join(",")),
+        ("cdp_runtime", "not detected".to_string()),
+        ("to_string", "native".to_string()),
+        ("iframe", "consistent".to_string()),
+        ("worker", "consistent".to_string()),
+        ("client_hints_brands", brands),
+        ("client_hints_platform", hints_platform),
+        ("ua_client_hints", "consistent".to_string()),
+        ("webgl_vendor", profile.webgl_vendor().to_string()),
+        ("webgl_renderer", profile.webgl_renderer().to_string()),
+        ("plugins", plugins.to_string()),
+        ("permissions", "consistent".to_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    #[test]
+    fn report_compares_observations_with_the_profile() {
+        let profile = crate::evasions::fixture();
+        let observed = serde_json::json!({
+            "webdriver": "false",
+            "platform": "Linux x86_64",
+            "worker": "hardwareConcurrency: 12 vs 2",
+        });
+        let report = AuditReport::new(&observed, expectations(&profile));
+
+        assert!(report.check("webdriver").unwrap().passed);
+        let platform = report.check("platform").unwrap();
+        assert!(!platform.passed);
+        assert_eq!(platform.expected, "Win32");
+        assert_eq!(report.check("plugins").unwrap().observed, "missing");
+        assert!(!report.passed());
+        assert!(report.to_string().contains("FAIL worker"));
+    }
+
+    #[test]
+    fn server_serves_the_probes() {
+        let server = ProbeServer::start().unwrap();
+        let mut stream = TcpStream::connect(server.addr()).unwrap();
+        stream
+            .write_all(b"GET /worker.js?v=1 HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("application/javascript"));
+        assert!(response.contains("postMessage"));
+        assert!(server.url("/").starts_with("http://127.0.0.1:"));
+    }
+}
@@ -0,0 +1,184 @@
+//! The pages served by the [`ProbeServer`](super::ProbeServer).
+//!
+//! `index.html` runs every probe in the page's main world, where detection
+//! scripts run too, and stores the observations as JSON in
+//! `document.documentElement.dataset.audit`.
+
+/// Returns the content type and body served for `path`
+pub(crate) fn asset(path: &str) -> Option<(&'static str, &'static str)> {
+    match path {
+        "/" | "/index.html" => Some(("text/html", INDEX_HTML)),
+        "/frame.html" => Some(("text/html", FRAME_HTML)),
+        "/worker.js" => Some(("application/javascript", WORKER_JS)),
+        _ => None,
+    }
+}
+
+const INDEX_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>chaser-oxide fingerprint audit</title>
+</head>
+<body>
+<iframe id="frame" src="/frame.html"></iframe>
+<script>
+(async () => {
+    const results = {};
+    const report = (name, observed) => { results[name] = String(observed); };
+    const probe = async (name, fn) => {
+        try {
+            await fn();
+        } catch (e) {
+            report(name, 'error: ' + e.message);
+        }
+    };
+    const snapshot = (nav) => ({
+        userAgent: nav.userAgent,
+        platform: nav.platform,
+        hardwareConcurrency: nav.hardwareConcurrency,
+        deviceMemory: nav.deviceMemory,
+        languages: (nav.languages || []).join(','),
+        uaPlatform: nav.userAgentData ? nav.userAgentData.platform : 'none'
+    });
+    const compare = (name, other) => {
+        const top = snapshot(navigator);
+        const diff = Object.keys(other)
+            .filter((key) => String(top[key]) !== String(other[key]))
+            .map((key) => key + ': ' + top[key] + ' vs ' + other[key]);
+        report(name, diff.length ? diff.join('; ') : 'consistent');
+    };
+
+    await probe('webdriver', () => report('webdriver', navigator.webdriver));
+    await probe('user_agent', () => report('user_agent', navigator.userAgent));
+    await probe('platform', () => report('platform', navigator.platform));
+    await probe('hardware_concurrency', () => report('hardware_concurrency', navigator.hardwareConcurrency));
+    await probe('device_memory', () => report('device_memory', navigator.deviceMemory));
+    await probe('languages', () => report('languages', navigator.languages.join(',')));
+
+    // devtools serializes logged errors, reading their stack, only while the
+    // Runtime domain is enabled
+    await probe('cdp_runtime', async () => {
+        let leaked = false;
+        const err = new Error('audit');
+        Object.defineProperty(err, 'stack', {
+            configurable: true,
+            get() {
+                leaked = true;
+                return '';
+            }
+        });
+        console.debug(err);
+        await new Promise((resolve) => setTimeout(resolve, 50));
+        report('cdp_runtime', leaked ? 'Runtime domain enabled' : 'not detected');
+    });
+
+    await probe('to_string', () => {
+        const getter = (proto, prop) => {
+            const desc = Object.getOwnPropertyDescriptor(proto, prop);
+            return desc && desc.get;
+        };
+        const functions = {
+            'Function.prototype.toString': Function.prototype.toString,
+            'HTMLMediaElement.prototype.canPlayType': HTMLMediaElement.prototype.canPlayType,
+            'Permissions.prototype.query': Permissions.prototype.query,
+            'WebGLRenderingContext.prototype.getParameter': WebGLRenderingContext.prototype.getParameter,
+            'get Navigator.prototype.platform': getter(Navigator.prototype, 'platform'),
+            'get Navigator.prototype.hardwareConcurrency': getter(Navigator.prototype, 'hardwareConcurrency'),
+            'get Navigator.prototype.webdriver': getter(Navigator.prototype, 'webdriver')
+        };
+        const isNative = (fn) => typeof fn === 'function'
+            && /^function [^(]*\(\) \{\s*\[native code\]\s*\}$/.test(Function.prototype.toString.call(fn))
+            && !Object.prototype.hasOwnProperty.call(fn, 'prototype');
+        const patched = Object.keys(functions).filter((name) => !isNative(functions[name]));
+        report('to_string', patched.length ? patched.join(', ') : 'native');
+    });
+
+    await probe('iframe', async () => {
+        const frame = document.getElementById('frame');
+        if (!frame.contentDocument || frame.contentDocument.readyState !== 'complete') {
+            await new Promise((resolve) => frame.addEventListener('load', resolve, { once: true }));
+        }
+        compare('iframe', snapshot(frame.contentWindow.navigator));
+    });
+
+    await probe('worker', async () => {
+        const worker = new Worker('/worker.js');
+        const observed = await Promise.race([
+            new Promise((resolve) => { worker.onmessage = (event) => resolve(event.data); }),
+            new Promise((resolve) => setTimeout(() => resolve(null), 3000))
+        ]);
+        worker.terminate();
+        if (observed) compare('worker', observed);
+        else report('worker', 'no response');
+    });
+
+    await probe('client_hints_brands', () => {
+        const uaData = navigator.userAgentData;
+        report('client_hints_brands', uaData
+            ? uaData.brands.map((b) => b.brand + ' ' + b.version).join(', ')
+            : 'none');
+    });
+    await probe('client_hints_platform', () => {
+        report('client_hints_platform', navigator.userAgentData ? navigator.userAgentData.platform : 'none');
+    });
+    await probe('ua_client_hints', () => {
+        const uaData = navigator.userAgentData;
+        const version = /Chrome\/([0-9]+)/.exec(navigator.userAgent);
+        if (!uaData) {
+            report('ua_client_hints', version ? 'userAgentData missing' : 'consistent');
+            return;
+        }
+        const brand = uaData.brands.find((b) => b.brand === 'Google Chrome' || b.brand === 'Chromium');
+        const major = version ? version[1] : 'none';
+        report('ua_client_hints', brand && brand.version === major
+            ? 'consistent'
+            : 'Chrome/' + major + ' vs ' + (brand ? brand.brand + ' ' + brand.version : 'no Chrome brand'));
+    });
+
+    await probe('webgl_vendor', () => {
+        const gl = document.createElement('canvas').getContext('webgl');
+        const info = gl && gl.getExtension('WEBGL_debug_renderer_info');
+        report('webgl_vendor', info ? gl.getParameter(info.UNMASKED_VENDOR_WEBGL) : 'unavailable');
+        report('webgl_renderer', info ? gl.getParameter(info.UNMASKED_RENDERER_WEBGL) : 'unavailable');
+    });
+
+    await probe('plugins', () => {
+        const plugins = navigator.plugins;
+        report('plugins', plugins instanceof PluginArray
+            ? plugins.length + ' plugins, ' + navigator.mimeTypes.length + ' mime types'
+            : 'not a PluginArray');
+    });
+
+    await probe('permissions', async () => {
+        const status = await navigator.permissions.query({ name: 'notifications' });
+        const expected = Notification.permission === 'default' ? 'prompt' : Notification.permission;
+        report('permissions', status.state === expected
+            ? 'consistent'
+            : 'Notification.permission ' + Notification.permission + ' vs query ' + status.state);
+    });
+
+    document.documentElement.dataset.audit = JSON.stringify(results);
+})();
+</script>
+</body>
+</html>
+"#;
+
+const FRAME_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body></body>
+</html>
+"#;
+
+const WORKER_JS: &str = r#"
+postMessage({
+    userAgent: navigator.userAgent,
+    platform: navigator.platform,
+    hardwareConcurrency: navigator.hardwareConcurrency,
+    deviceMemory: navigator.deviceMemory,
+    languages: (navigator.languages || []).join(','),
+    uaPlatform: navigator.userAgentData ? navigator.userAgentData.platform : 'none'
+});
+"#;
@@ -139,6 +139,16 @@ impl Element {
 
     /// Returns the best `Point` of this node to execute a click on.
     pub async fn clickable_point(&self) -> Result<Point> {
+        self.content_quads()
+            .await?
+            .first()
+            .map(ElementQuad::quad_center)
+            .ok_or_else(|| CdpError::msg("Node is either not visible or not an HTMLElement"))
+    }
+
+    /// Returns the visible quads of this node in viewport coordinates, an
+    /// inline element wrapping over several lines has one per line.
+    pub async fn content_quads(&self) -> Result<Vec<ElementQuad>> {
         let content_quads = self
             .tab
             .execute(
@@ -147,15 +157,13 @@ impl Element {
                     .build(),
             )
             .await?;
-        content_quads
+        Ok(content_quads
             .quads
             .iter()
             .filter(|q| q.inner().len() == 8)
             .map(ElementQuad::from_quad)
             .filter(|q| q.quad_area() > 1.)
-            .map(|q| q.quad_center())
-            .next()
-            .ok_or_else(|| CdpError::msg("Node is either not visible or not an HTMLElement"))
+            .collect())
     }
 
     /// Submits a javascript function to the page and returns the evaluated
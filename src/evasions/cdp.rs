@@ -0,0 +1,77 @@
+//! Traces of the automation protocol itself.
+
+use super::Evasion;
+use crate::stealth::StealthProfile;
+
+/// Removes the `cdc_`/`$cdc_` globals and other markers drivers leave on
+/// `window`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CdpMarkers;
+
+impl Evasion for CdpMarkers {
+    fn name(&self) -> &str {
+        "cdp_markers"
+    }
+
+    fn script(&self, _profile: &dyn StealthProfile) -> String {
+        CDP_MARKERS_SCRIPT.to_string()
+    }
+}
+
+/// Ignores writes to `Error.prepareStackTrace`, which detection scripts use
+/// to notice the protocol serializing error objects
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StackTrace;
+
+impl Evasion for StackTrace {
+    fn name(&self) -> &str {
+        "stack_trace"
+    }
+
+    fn script(&self, _profile: &dyn StealthProfile) -> String {
+        STACK_TRACE_SCRIPT.to_string()
+    }
+}
+
+const CDP_MARKERS_SCRIPT: &str = r#"
+                for (const prop of Object.getOwnPropertyNames(window)) {
+                    if (/^cdc_|^\$cdc_|^__webdriver|^__selenium|^__driver|^\$chrome_/.test(prop)) {
+                        try { delete window[prop]; } catch(e) {}
+                    }
+                }
+"#;
+
+const STACK_TRACE_SCRIPT: &str = r#"
+                const OriginalError = Error;  
+                const originalPrepareStackTrace = Error.prepareStackTrace;    
+                let currentPrepareStackTrace = originalPrepareStackTrace;    
+                Object.defineProperty(Error, 'prepareStackTrace', {    
+                    get: native(function() {
+                        return currentPrepareStackTrace;
+                    }, 'get prepareStackTrace', 0),
+                    set: native(function(fn) {
+                        // do nothing to prevent detection of CDP
+                    }, 'set prepareStackTrace', 1),
+                    configurable: true,    
+                    enumerable: false  
+                });
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evasions::fixture;
+
+    #[test]
+    fn cdp_markers() {
+        let script = CdpMarkers.script(&fixture());
+        assert!(script.contains("Object.getOwnPropertyNames(window)"));
+        assert!(script.contains("cdc_"));
+    }
+
+    #[test]
+    fn stack_trace() {
+        let script = StackTrace.script(&fixture());
+        assert!(script.contains("Object.defineProperty(Error, 'prepareStackTrace'"));
+    }
+}
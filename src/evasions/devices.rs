@@ -0,0 +1,240 @@
+//! Device APIs next to `navigator` that headless Chrome leaves empty or
+//! answers with impossible values.
+
+use serde_json::{json, Value};
+
+use super::Evasion;
+use crate::profiles::fnv1a;
+use crate::stealth::StealthProfile;
+
+/// Reports the profile's `navigator.connection`, or removes it
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NetworkInfo;
+
+impl Evasion for NetworkInfo {
+    fn name(&self) -> &str {
+        "connection"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        let connection = profile.connection().map(|c| {
+            json!({
+                "effectiveType": c.effective_type,
+                "rtt": c.rtt,
+                "downlink": c.downlink,
+                "saveData": c.save_data,
+            })
+        });
+        CONNECTION_SCRIPT.replace(
+            "__CONNECTION__",
+            &connection.unwrap_or(Value::Null).to_string(),
+        )
+    }
+}
+
+/// Resolves `navigator.getBattery()` with the profile's battery, or removes it
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatteryStatus;
+
+impl Evasion for BatteryStatus {
+    fn name(&self) -> &str {
+        "battery"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        let battery = profile.battery().map(|b| {
+            json!({
+                "level": b.level,
+                "charging": b.charging,
+                "chargingTime": b.charging_time,
+                "dischargingTime": b.discharging_time,
+            })
+        });
+        BATTERY_SCRIPT.replace("__BATTERY__", &battery.unwrap_or(Value::Null).to_string())
+    }
+}
+
+/// Lists the profile's media devices while the browser reports none
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MediaDeviceList;
+
+impl Evasion for MediaDeviceList {
+    fn name(&self) -> &str {
+        "media_devices"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        let devices = profile.media_devices();
+        // group ids are stable per profile, like the per-origin hashes Chrome
+        // hands out
+        let seed = profile
+            .noise_seed()
+            .unwrap_or_else(|| fnv1a(profile.user_agent().as_bytes()));
+        let group_id = |group: &str| {
+            (0..8u32)
+                .map(|i| format!("{:08x}", fnv1a(format!("{i}|{group}|{seed}").as_bytes())))
+                .collect::<String>()
+        };
+        // a microphone and speakers of the same index belong to one headset
+        let list = [
+            ("audioinput", devices.audio_inputs, "audio"),
+            ("videoinput", devices.video_inputs, "video"),
+            ("audiooutput", devices.audio_outputs, "audio"),
+        ]
+        .into_iter()
+        .flat_map(|(kind, count, group)| (0..count).map(move |i| (kind, format!("{group}{i}"))))
+        .map(|(kind, group)| json!({ "kind": kind, "groupId": group_id(&group) }))
+        .collect::<Vec<_>>();
+        MEDIA_DEVICES_SCRIPT.replace("__DEVICES__", &Value::from(list).to_string())
+    }
+}
+
+/// Returns the profile's voices from `speechSynthesis.getVoices()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpeechVoices;
+
+impl Evasion for SpeechVoices {
+    fn name(&self) -> &str {
+        "speech_voices"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        let voices = profile
+            .speech_voices()
+            .into_iter()
+            .map(|v| {
+                json!({
+                    "voiceURI": v.name,
+                    "name": v.name,
+                    "lang": v.lang,
+                    "localService": v.local_service,
+                    "default": v.default,
+                })
+            })
+            .collect::<Vec<_>>();
+        SPEECH_VOICES_SCRIPT.replace("__VOICES__", &Value::from(voices).to_string())
+    }
+}
+
+/// `NetworkInformation` exists in workers as well
+const CONNECTION_SCRIPT: &str = r#"
+                const connection = __CONNECTION__;
+                if (connection === null) {
+                    delete Navigator.prototype.connection;
+                    delete globalThis.NetworkInformation;
+                } else if (typeof NetworkInformation !== 'undefined') {
+                    for (const prop of ['effectiveType', 'rtt', 'downlink', 'saveData']) {
+                        defineGetter(NetworkInformation.prototype, prop, () => connection[prop]);
+                    }
+                }
+"#;
+
+/// The getters don't touch the receiver, so a `BatteryManager` created from
+/// its prototype works where the API is missing
+const BATTERY_SCRIPT: &str = r#"
+                const battery = __BATTERY__;
+                if (battery === null) {
+                    delete Navigator.prototype.getBattery;
+                    delete globalThis.BatteryManager;
+                } else if (typeof BatteryManager !== 'undefined') {
+                    const seconds = (value) => (value === null ? Infinity : value);
+                    defineGetter(BatteryManager.prototype, 'level', () => battery.level);
+                    defineGetter(BatteryManager.prototype, 'charging', () => battery.charging);
+                    defineGetter(BatteryManager.prototype, 'chargingTime', () => seconds(battery.chargingTime));
+                    defineGetter(BatteryManager.prototype, 'dischargingTime', () => seconds(battery.dischargingTime));
+                    if (typeof Navigator.prototype.getBattery !== 'function') {
+                        const manager = Object.create(BatteryManager.prototype);
+                        Navigator.prototype.getBattery = native(function() {
+                            return Promise.resolve(manager);
+                        }, 'getBattery', 0);
+                    }
+                }
+"#;
+
+/// Until a permission is granted Chrome lists devices without id or label
+const MEDIA_DEVICES_SCRIPT: &str = r#"
+                const devices = __DEVICES__;
+                if (typeof MediaDevices !== 'undefined' && typeof MediaDeviceInfo !== 'undefined') {
+                    const fakeDevices = new WeakMap();
+                    const props = ['deviceId', 'kind', 'label', 'groupId'];
+                    for (const prop of props) {
+                        const desc = Object.getOwnPropertyDescriptor(MediaDeviceInfo.prototype, prop);
+                        defineGetter(MediaDeviceInfo.prototype, prop, function() {
+                            if (fakeDevices.has(this)) return fakeDevices.get(this)[prop];
+                            return desc.get.call(this);
+                        });
+                    }
+                    const toJSON = MediaDeviceInfo.prototype.toJSON;
+                    MediaDeviceInfo.prototype.toJSON = native(function() {
+                        if (!fakeDevices.has(this)) return toJSON.apply(this, arguments);
+                        const device = fakeDevices.get(this);
+                        const json = {};
+                        for (const prop of props) json[prop] = device[prop];
+                        return json;
+                    }, toJSON);
+                    const createDevice = (device) => {
+                        const input = device.kind !== 'audiooutput' && typeof InputDeviceInfo !== 'undefined';
+                        const info = Object.create(input ? InputDeviceInfo.prototype : MediaDeviceInfo.prototype);
+                        fakeDevices.set(info, { deviceId: '', kind: device.kind, label: '', groupId: device.groupId });
+                        return info;
+                    };
+                    const enumerateDevices = MediaDevices.prototype.enumerateDevices;
+                    MediaDevices.prototype.enumerateDevices = native(function() {
+                        return enumerateDevices.apply(this, arguments)
+                            .then((real) => (real.length ? real : devices.map(createDevice)));
+                    }, enumerateDevices);
+                }
+"#;
+
+const SPEECH_VOICES_SCRIPT: &str = r#"
+                const voices = __VOICES__;
+                if (typeof SpeechSynthesis !== 'undefined' && typeof SpeechSynthesisVoice !== 'undefined') {
+                    const fakeVoices = new WeakMap();
+                    for (const prop of ['voiceURI', 'name', 'lang', 'localService', 'default']) {
+                        const desc = Object.getOwnPropertyDescriptor(SpeechSynthesisVoice.prototype, prop);
+                        defineGetter(SpeechSynthesisVoice.prototype, prop, function() {
+                            if (fakeVoices.has(this)) return fakeVoices.get(this)[prop];
+                            return desc.get.call(this);
+                        });
+                    }
+                    const voiceList = voices.map((voice) => {
+                        const instance = Object.create(SpeechSynthesisVoice.prototype);
+                        fakeVoices.set(instance, voice);
+                        return instance;
+                    });
+                    const getVoices = SpeechSynthesis.prototype.getVoices;
+                    SpeechSynthesis.prototype.getVoices = native(function() {
+                        return voiceList.slice();
+                    }, getVoices);
+                }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evasions::fixture;
+    use crate::profiles::ChaserProfile;
+
+    #[test]
+    fn device_apis() {
+        let connection = NetworkInfo.script(&fixture());
+        assert!(connection.contains(r#""effectiveType":"4g","rtt":50"#));
+        let battery = BatteryStatus.script(&fixture());
+        assert!(battery.contains(r#""charging":true,"chargingTime":0,"dischargingTime":null"#));
+
+        let devices = MediaDeviceList.script(&fixture());
+        assert_eq!(devices.matches(r#""kind":"audioinput""#).count(), 1);
+        let group = devices.split(r#""groupId":""#).nth(1).unwrap();
+        assert_eq!(group.find('"'), Some(64));
+        assert_eq!(devices, MediaDeviceList.script(&fixture()));
+
+        let voices = SpeechVoices.script(&fixture());
+        assert!(voices.contains(r#""name":"Microsoft David - English (United States)""#));
+
+        let ios = ChaserProfile::ios().build();
+        assert!(NetworkInfo
+            .script(&ios)
+            .contains("const connection = null;"));
+        assert!(BatteryStatus.script(&ios).contains("const battery = null;"));
+    }
+}
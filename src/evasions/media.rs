@@ -0,0 +1,190 @@
+//! Graphics, media and font fingerprints.
+
+use serde_json::Value;
+
+use super::{js_string, Evasion};
+use crate::profiles::{fonts_script, noise_script};
+use crate::stealth::StealthProfile;
+
+/// Reports the profile's GPU through WebGL: vendor and renderer strings,
+/// limits, extensions and shader precision
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WebGl;
+
+impl Evasion for WebGl {
+    fn name(&self) -> &str {
+        "webgl"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        let capabilities = match profile.webgl_capabilities() {
+            Some(capabilities) => capabilities.to_json(),
+            None => Value::Null,
+        };
+        WEBGL_SCRIPT
+            .replace("__WEBGL__", &capabilities.to_string())
+            .replace("__VENDOR__", &js_string(profile.webgl_vendor()))
+            .replace("__RENDERER__", &js_string(profile.webgl_renderer()))
+    }
+}
+
+/// Reports H.264 and AAC support, which Chromium builds without proprietary
+/// codecs lack
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Codecs;
+
+impl Evasion for Codecs {
+    fn name(&self) -> &str {
+        "codecs"
+    }
+
+    fn script(&self, _profile: &dyn StealthProfile) -> String {
+        CODECS_SCRIPT.to_string()
+    }
+}
+
+/// Seeded canvas, WebGL readback and audio noise, see
+/// [`StealthProfile::noise_seed`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Noise;
+
+impl Evasion for Noise {
+    fn name(&self) -> &str {
+        "noise"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        noise_script(profile.noise_seed())
+    }
+}
+
+/// Hides fonts outside of [`StealthProfile::fonts`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fonts;
+
+impl Evasion for Fonts {
+    fn name(&self) -> &str {
+        "fonts"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        fonts_script(profile.fonts().as_deref())
+    }
+}
+
+/// Without capabilities only the vendor and renderer strings are spoofed
+const WEBGL_SCRIPT: &str = r#"
+                const webgl = __WEBGL__ || { params: {}, params2: {} };
+                const shaderPrecisions = new WeakMap();
+                if (typeof WebGLShaderPrecisionFormat !== 'undefined') {
+                    ['rangeMin', 'rangeMax', 'precision'].forEach((key, i) => {
+                        const desc = Object.getOwnPropertyDescriptor(WebGLShaderPrecisionFormat.prototype, key);
+                        defineGetter(WebGLShaderPrecisionFormat.prototype, key, function() {
+                            const spoofed = shaderPrecisions.get(this);
+                            return spoofed ? spoofed[i] : desc.get.call(this);
+                        });
+                    });
+                }
+                // MAX_TEXTURE_MAX_ANISOTROPY_EXT is an invalid enum until the
+                // extension has been enabled on the context
+                const anisotropic = new WeakSet();
+                const spoofWebGL = (proto, params, extensions) => {
+                    const getParameter = proto.getParameter;
+                    proto.getParameter = native(function(parameter) {
+                        if (parameter === 37445) return __VENDOR__;
+                        if (parameter === 37446) return __RENDERER__;
+                        if (parameter === 34047 && !anisotropic.has(this)) {
+                            return getParameter.apply(this, arguments);
+                        }
+                        const value = params[parameter];
+                        if (value === undefined) return getParameter.apply(this, arguments);
+                        if (!Array.isArray(value)) return value;
+                        // MAX_VIEWPORT_DIMS is the only integer array
+                        return parameter === 3386 ? new Int32Array(value) : new Float32Array(value);
+                    }, getParameter);
+                    const getExtension = proto.getExtension;
+                    proto.getExtension = native(function(name) {
+                        const wanted = String(name).toLowerCase();
+                        if (extensions && !extensions.some((e) => e.toLowerCase() === wanted)) return null;
+                        const extension = getExtension.apply(this, arguments);
+                        if (extension && wanted.endsWith('ext_texture_filter_anisotropic')) {
+                            anisotropic.add(this);
+                        }
+                        return extension;
+                    }, getExtension);
+                    if (!extensions) return;
+                    // Only list what getExtension can hand out as well, a
+                    // weaker real GPU would otherwise return null for
+                    // extensions the page was told are supported
+                    const getSupportedExtensions = proto.getSupportedExtensions;
+                    proto.getSupportedExtensions = native(function() {
+                        const real = getSupportedExtensions.apply(this, arguments);
+                        if (!real) return real;
+                        const available = new Set(real.map((e) => e.toLowerCase()));
+                        return extensions.filter((e) => available.has(e.toLowerCase()));
+                    }, getSupportedExtensions);
+                    const getShaderPrecisionFormat = proto.getShaderPrecisionFormat;
+                    proto.getShaderPrecisionFormat = native(function(shaderType, precisionType) {
+                        const format = getShaderPrecisionFormat.apply(this, arguments);
+                        // LOW_FLOAT..HIGH_FLOAT are 0x8DF0..0x8DF2, LOW_INT..HIGH_INT 0x8DF3..0x8DF5
+                        const index = precisionType - 0x8DF0;
+                        if (format && index >= 0 && index < 6) {
+                            shaderPrecisions.set(format, index < 3 ? webgl.floatPrecision[index] : webgl.intPrecision[index - 3]);
+                        }
+                        return format;
+                    }, getShaderPrecisionFormat);
+                };
+                spoofWebGL(WebGLRenderingContext.prototype, webgl.params, webgl.extensions);
+                if (typeof WebGL2RenderingContext !== 'undefined') {
+                    spoofWebGL(
+                        WebGL2RenderingContext.prototype,
+                        Object.assign({}, webgl.params, webgl.params2),
+                        webgl.extensions2
+                    );
+                }
+"#;
+
+const CODECS_SCRIPT: &str = r#"
+                const canPlayType = HTMLMediaElement.prototype.canPlayType;
+                HTMLMediaElement.prototype.canPlayType = native(function(type) {
+                    type = String(type);
+                    if (type.includes('avc1')) return 'probably';
+                    if (type.includes('mp4a.40')) return 'probably';
+                    if (type === 'video/mp4') return 'probably';
+                    return canPlayType.apply(this, arguments);
+                }, canPlayType);
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evasions::fixture;
+    use crate::profiles::LinuxProfile;
+
+    #[test]
+    fn webgl() {
+        let script = WebGl.script(&fixture());
+        assert!(script.contains("NVIDIA GeForce RTX 4080"));
+        assert!(script.contains(r#""extensions2":["#));
+
+        // custom profiles without capabilities keep the real limits
+        let legacy = WebGl.script(&LinuxProfile);
+        assert!(legacy.contains("const webgl = null || "));
+    }
+
+    #[test]
+    fn codecs() {
+        assert!(Codecs.script(&fixture()).contains("type.includes('avc1')"));
+    }
+
+    #[test]
+    fn noise() {
+        assert!(Noise.script(&fixture()).contains("const noiseSeed = 42;"));
+    }
+
+    #[test]
+    fn fonts() {
+        assert!(Fonts.script(&fixture()).contains(r#""Segoe UI""#));
+        assert!(Fonts.script(&LinuxProfile).is_empty());
+    }
+}
@@ -0,0 +1,367 @@
+//! Composable evasion modules.
+//!
+//! The bootstrap script of a profile is assembled from named [`Evasion`]s,
+//! each patching one surface a detection script looks at. An [`EvasionSet`]
+//! picks the modules, so a patch that breaks a site can be turned off on its
+//! own and custom patches go through the same pipeline:
+//!
+//! ```rust
+//! use chaser_oxide::evasions::{Evasion, EvasionSet};
+//! use chaser_oxide::profiles::{ChaserProfile, StealthProfile};
+//!
+//! struct HideBattery;
+//!
+//! impl Evasion for HideBattery {
+//!     fn name(&self) -> &str {
+//!         "hide_battery"
+//!     }
+//!
+//!     fn script(&self, _profile: &dyn StealthProfile) -> String {
+//!         "delete Navigator.prototype.getBattery;".to_string()
+//!     }
+//! }
+//!
+//! let evasions = EvasionSet::builder()
+//!     .disable("stack_trace")
+//!     .with(HideBattery)
+//!     .build();
+//! let script = evasions.script(&ChaserProfile::windows().build());
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::stealth::StealthProfile;
+
+mod cdp;
+mod devices;
+mod media;
+mod navigator;
+mod plugins;
+mod webrtc;
+mod window;
+
+pub use cdp::{CdpMarkers, StackTrace};
+pub use devices::{BatteryStatus, MediaDeviceList, NetworkInfo, SpeechVoices};
+pub use media::{Codecs, Fonts, Noise, WebGl};
+pub use navigator::{ClientHints, Hardware, Languages, Permissions, Platform, WebDriver};
+pub use plugins::Plugins;
+pub use webrtc::WebRtc;
+pub use window::{ChromeObject, Screen};
+
+/// A single patch of the bootstrap script
+pub trait Evasion: Send + Sync {
+    /// The name the module is enabled or disabled by
+    fn name(&self) -> &str;
+
+    /// Returns the JavaScript applying this patch for `profile`, empty if
+    /// there is nothing to patch.
+    ///
+    /// Every module runs in its own block scope, a module that throws doesn't
+    /// stop the ones after it. Patched functions should go through the
+    /// runtime helpers so they pass as native code:
+    ///
+    /// - `native(impl, original)` or `native(impl, name, length)` returns a
+    ///   method calling `impl` with the name, length and `[native code]`
+    ///   source of a builtin, and without a `prototype`
+    /// - `defineGetter(target, prop, getter)` replaces an accessor with a
+    ///   native looking getter, keeping its setter and enumerability
+    fn script(&self, profile: &dyn StealthProfile) -> String;
+}
+
+/// An ordered set of [`Evasion`]s that renders the bootstrap script
+#[derive(Clone)]
+pub struct EvasionSet {
+    evasions: Vec<Arc<dyn Evasion>>,
+}
+
+impl EvasionSet {
+    /// Returns a builder starting from all builtin modules
+    pub fn builder() -> EvasionSetBuilder {
+        EvasionSetBuilder {
+            evasions: builtin(),
+        }
+    }
+
+    /// Returns a builder starting without any module
+    pub fn empty() -> EvasionSetBuilder {
+        EvasionSetBuilder {
+            evasions: Vec::new(),
+        }
+    }
+
+    /// Returns the names of the enabled modules in the order they run
+    pub fn names(&self) -> Vec<&str> {
+        self.evasions.iter().map(|e| e.name()).collect()
+    }
+
+    /// Whether the module `name` is enabled
+    pub fn contains(&self, name: &str) -> bool {
+        self.evasions.iter().any(|e| e.name() == name)
+    }
+
+    /// Generate the complete JavaScript bootstrap script for `profile`.
+    ///
+    /// The same script runs in documents and in workers, where `Navigator`
+    /// refers to `WorkerNavigator` and window-only modules are skipped.
+    pub fn script(&self, profile: &dyn StealthProfile) -> String {
+        let mut body = RUNTIME_SCRIPT.to_string();
+        for evasion in &self.evasions {
+            let script = evasion.script(profile);
+            if script.trim().is_empty() {
+                continue;
+            }
+            body.push_str(&format!(
+                "\n                // {}\n                try {{{}\n                }} catch (e) {{}}\n",
+                evasion.name(),
+                script.trim_end()
+            ));
+        }
+
+        format!(
+            r#"
+            (function() {{
+                // === chaser-oxide HARDWARE HARMONY ===
+                // Profile: {}
+{}
+            }})();
+        "#,
+            profile.user_agent(),
+            body
+        )
+    }
+}
+
+impl Default for EvasionSet {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl fmt::Debug for EvasionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvasionSet")
+            .field("evasions", &self.names())
+            .finish()
+    }
+}
+
+/// Builder for [`EvasionSet`]
+#[derive(Clone)]
+pub struct EvasionSetBuilder {
+    evasions: Vec<Arc<dyn Evasion>>,
+}
+
+impl EvasionSetBuilder {
+    /// Appends a module, replacing an enabled module of the same name in place
+    pub fn with(mut self, evasion: impl Evasion + 'static) -> Self {
+        let evasion: Arc<dyn Evasion> = Arc::new(evasion);
+        match self
+            .evasions
+            .iter_mut()
+            .find(|e| e.name() == evasion.name())
+        {
+            Some(existing) => *existing = evasion,
+            None => self.evasions.push(evasion),
+        }
+        self
+    }
+
+    /// Removes the module `name`
+    pub fn disable(mut self, name: &str) -> Self {
+        self.evasions.retain(|e| e.name() != name);
+        self
+    }
+
+    /// Build the final set
+    pub fn build(self) -> EvasionSet {
+        EvasionSet {
+            evasions: self.evasions,
+        }
+    }
+}
+
+impl fmt::Debug for EvasionSetBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvasionSetBuilder")
+            .field(
+                "evasions",
+                &self.evasions.iter().map(|e| e.name()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// The builtin modules in the order they run
+fn builtin() -> Vec<Arc<dyn Evasion>> {
+    vec![
+        Arc::new(CdpMarkers),
+        Arc::new(StackTrace),
+        Arc::new(Platform),
+        Arc::new(Hardware),
+        Arc::new(WebGl),
+        Arc::new(ClientHints),
+        Arc::new(Codecs),
+        Arc::new(WebDriver),
+        Arc::new(ChromeObject),
+        Arc::new(Screen),
+        Arc::new(Languages),
+        Arc::new(Plugins),
+        Arc::new(NetworkInfo),
+        Arc::new(BatteryStatus),
+        Arc::new(MediaDeviceList),
+        Arc::new(SpeechVoices),
+        Arc::new(Noise),
+        Arc::new(Fonts),
+        Arc::new(WebRtc),
+        Arc::new(Permissions),
+    ]
+}
+
+/// Quotes `value` as a JavaScript string literal
+fn js_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+/// Helpers shared by all modules, see [`Evasion::script`].
+///
+/// In workers `Navigator` is shadowed by `WorkerNavigator`, so the navigator
+/// modules patch the worker's `navigator` without changes.
+///
+/// `Function.prototype.toString` reports registered functions as native. For
+/// functions of another realm it defers to that realm's `toString`, and
+/// realms reached through `contentWindow` before their own script ran get
+/// patched on access, so cross-realm checks see the same source.
+const RUNTIME_SCRIPT: &str = r#"
+                // workers patch WorkerNavigator and don't gain properties
+                // that only exist on the page
+                const inWorker = typeof WorkerGlobalScope !== 'undefined' && globalThis instanceof WorkerGlobalScope;
+                const Navigator = inWorker ? globalThis.WorkerNavigator : globalThis.Navigator;
+
+                const nativeNames = new WeakMap();
+                const markNative = (fn, name) => {
+                    nativeNames.set(fn, name);
+                    return fn;
+                };
+                const native = (impl, original, length) => {
+                    const name = typeof original === 'function' ? original.name : original;
+                    const fn = {
+                        [name](...args) {
+                            return Reflect.apply(impl, this, args);
+                        }
+                    }[name];
+                    if (typeof original === 'function') length = original.length;
+                    Object.defineProperty(fn, 'length', {
+                        value: length === undefined ? impl.length : length,
+                        configurable: true
+                    });
+                    return markNative(fn, name);
+                };
+                const defineGetter = (target, prop, getter) => {
+                    const desc = Object.getOwnPropertyDescriptor(target, prop);
+                    if (!desc && inWorker) return;
+                    Object.defineProperty(target, prop, {
+                        get: native(getter, 'get ' + prop, 0),
+                        set: desc ? desc.set : undefined,
+                        configurable: true,
+                        enumerable: desc ? desc.enumerable : true
+                    });
+                };
+
+                const isFunctionPrototype = (proto) => {
+                    try {
+                        return typeof proto === 'function' && proto.constructor.prototype === proto
+                            && proto.constructor.name === 'Function';
+                    } catch (e) {
+                        return false;
+                    }
+                };
+                const patchToString = (FunctionProto) => {
+                    const realToString = FunctionProto.toString;
+                    const toString = native(function() {
+                        if (nativeNames.has(this)) return 'function ' + nativeNames.get(this) + '() { [native code] }';
+                        const proto = typeof this === 'function' ? Object.getPrototypeOf(this) : null;
+                        if (proto !== FunctionProto && isFunctionPrototype(proto)) {
+                            return Reflect.apply(proto.toString, this, []);
+                        }
+                        return Reflect.apply(realToString, this, arguments);
+                    }, realToString);
+                    Object.setPrototypeOf(toString, FunctionProto);
+                    Object.defineProperty(FunctionProto, 'toString', {
+                        value: toString,
+                        writable: true,
+                        configurable: true,
+                        enumerable: false
+                    });
+                };
+                patchToString(Function.prototype);
+
+                if (typeof HTMLIFrameElement !== 'undefined') {
+                    const patchedRealms = new WeakSet([Function.prototype]);
+                    const contentWindow = Object.getOwnPropertyDescriptor(HTMLIFrameElement.prototype, 'contentWindow');
+                    defineGetter(HTMLIFrameElement.prototype, 'contentWindow', function() {
+                        const win = contentWindow.get.call(this);
+                        try {
+                            const FunctionProto = win && win.Function.prototype;
+                            if (FunctionProto && !patchedRealms.has(FunctionProto)) {
+                                patchedRealms.add(FunctionProto);
+                                patchToString(FunctionProto);
+                            }
+                        } catch (e) {}
+                        return win;
+                    });
+                }
+"#;
+
+/// The profile every module's fixture test renders
+#[cfg(test)]
+pub(crate) fn fixture() -> crate::profiles::ChaserProfile {
+    use crate::profiles::{ChaserProfile, Gpu};
+
+    ChaserProfile::windows()
+        .chrome_version(130)
+        .gpu(Gpu::NvidiaRTX4080)
+        .cpu_cores(12)
+        .memory_gb(16)
+        .locale("de-DE")
+        .screen(2560, 1440)
+        .noise_seed(42)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modules_can_be_disabled_and_replaced() {
+        let all = EvasionSet::default();
+        assert_eq!(all.names().len(), 20);
+        let script = all.script(&fixture());
+        assert!(script.contains("// stack_trace"));
+        // the native function helpers run before any module
+        let runtime = script.find("patchToString(Function.prototype);").unwrap();
+        assert!(runtime < script.find("// cdp_markers").unwrap());
+        // workers run the same script against WorkerNavigator
+        assert!(script.contains("inWorker ? globalThis.WorkerNavigator"));
+
+        struct Marker;
+        impl Evasion for Marker {
+            fn name(&self) -> &str {
+                "webdriver"
+            }
+            fn script(&self, _profile: &dyn StealthProfile) -> String {
+                "window.marker = true;".to_string()
+            }
+        }
+        let set = EvasionSet::builder()
+            .disable("stack_trace")
+            .with(Marker)
+            .build();
+        let script = set.script(&fixture());
+        assert!(!set.contains("stack_trace"));
+        assert!(!script.contains("prepareStackTrace"));
+        assert!(script.contains("window.marker = true;"));
+        assert_eq!(set.names()[6], "webdriver");
+    }
+}
@@ -0,0 +1,255 @@
+//! `navigator` properties that describe the device and the browser.
+
+use chromiumoxide_cdp::cdp::browser_protocol::emulation::UserAgentMetadata;
+use serde_json::Value;
+
+use super::{js_string, Evasion};
+use crate::stealth::StealthProfile;
+
+/// Reports the profile's `navigator.platform`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Platform;
+
+impl Evasion for Platform {
+    fn name(&self) -> &str {
+        "platform"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        PLATFORM_SCRIPT.replace("__PLATFORM__", &js_string(profile.platform()))
+    }
+}
+
+/// Reports the profile's CPU cores, memory and touch points
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hardware;
+
+impl Evasion for Hardware {
+    fn name(&self) -> &str {
+        "hardware"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        HARDWARE_SCRIPT
+            .replace("__CORES__", &profile.hardware_concurrency().to_string())
+            .replace("__MEMORY__", &profile.device_memory().to_string())
+            .replace("__TOUCH_POINTS__", &profile.max_touch_points().to_string())
+    }
+}
+
+/// Backs `navigator.userAgentData` with the profile's client hints, or
+/// removes it for browsers that don't send any
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClientHints;
+
+impl Evasion for ClientHints {
+    fn name(&self) -> &str {
+        "client_hints"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        CLIENT_HINTS_SCRIPT.replace(
+            "__UA_DATA__",
+            &user_agent_data_json(profile.user_agent_metadata()).to_string(),
+        )
+    }
+}
+
+/// Reports `navigator.webdriver` as `false`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WebDriver;
+
+impl Evasion for WebDriver {
+    fn name(&self) -> &str {
+        "webdriver"
+    }
+
+    fn script(&self, _profile: &dyn StealthProfile) -> String {
+        WEBDRIVER_SCRIPT.to_string()
+    }
+}
+
+/// Reports the profile's `navigator.language` and `navigator.languages`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Languages;
+
+impl Evasion for Languages {
+    fn name(&self) -> &str {
+        "languages"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        LANGUAGES_SCRIPT.replace(
+            "__LANGUAGES__",
+            &Value::from(profile.languages()).to_string(),
+        )
+    }
+}
+
+/// Makes the notifications permission agree with `Notification.permission`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Permissions;
+
+impl Evasion for Permissions {
+    fn name(&self) -> &str {
+        "permissions"
+    }
+
+    fn script(&self, _profile: &dyn StealthProfile) -> String {
+        PERMISSIONS_SCRIPT.to_string()
+    }
+}
+
+/// The client hints as seen by `navigator.userAgentData`, keyed by hint name
+fn user_agent_data_json(metadata: Option<UserAgentMetadata>) -> Value {
+    let Some(metadata) = metadata else {
+        return Value::Null;
+    };
+    // the legacy uaFullVersion hint is the full version of the browser brand
+    let full_version = metadata
+        .full_version_list
+        .iter()
+        .flatten()
+        .find(|b| b.brand == "Google Chrome" || b.brand == "Chromium")
+        .map(|b| b.version.clone());
+    serde_json::json!({
+        "brands": metadata.brands,
+        "fullVersionList": metadata.full_version_list,
+        "mobile": metadata.mobile,
+        "platform": metadata.platform,
+        "platformVersion": metadata.platform_version,
+        "architecture": metadata.architecture,
+        "bitness": metadata.bitness,
+        "model": metadata.model,
+        "wow64": metadata.wow64,
+        "formFactors": metadata.form_factors,
+        "uaFullVersion": full_version,
+    })
+}
+
+const PLATFORM_SCRIPT: &str = r#"
+                defineGetter(Navigator.prototype, 'platform', () => __PLATFORM__);
+"#;
+
+const HARDWARE_SCRIPT: &str = r#"
+                defineGetter(Navigator.prototype, 'hardwareConcurrency', () => __CORES__);
+                defineGetter(Navigator.prototype, 'deviceMemory', () => __MEMORY__);
+                defineGetter(Navigator.prototype, 'maxTouchPoints', () => __TOUCH_POINTS__);
+"#;
+
+/// `NavigatorUAData` only exists in secure contexts
+const CLIENT_HINTS_SCRIPT: &str = r#"
+                const uaData = __UA_DATA__;
+                if (uaData && typeof NavigatorUAData !== 'undefined') {
+                    const copyBrands = (list) => list.map((b) => ({ brand: b.brand, version: b.version }));
+                    const lowEntropy = () => ({
+                        brands: copyBrands(uaData.brands),
+                        mobile: uaData.mobile,
+                        platform: uaData.platform
+                    });
+                    defineGetter(NavigatorUAData.prototype, 'brands', () => Object.freeze(copyBrands(uaData.brands)));
+                    defineGetter(NavigatorUAData.prototype, 'mobile', () => uaData.mobile);
+                    defineGetter(NavigatorUAData.prototype, 'platform', () => uaData.platform);
+                    NavigatorUAData.prototype.getHighEntropyValues = native(async function(hints) {
+                        const values = lowEntropy();
+                        for (const hint of hints) {
+                            if (hint === 'fullVersionList') values.fullVersionList = copyBrands(uaData.fullVersionList);
+                            else if (hint in uaData) values[hint] = Array.isArray(uaData[hint]) ? [...uaData[hint]] : uaData[hint];
+                        }
+                        return values;
+                    }, 'getHighEntropyValues');
+                    NavigatorUAData.prototype.toJSON = native(function() { return lowEntropy(); }, 'toJSON');
+                } else if (!uaData) {
+                    // WebKit based browsers have no client hints at all
+                    delete Navigator.prototype.userAgentData;
+                    delete window.NavigatorUAData;
+                }
+"#;
+
+/// `false` rather than deleted, a missing property is just as suspicious
+const WEBDRIVER_SCRIPT: &str = r#"
+                defineGetter(Object.getPrototypeOf(navigator), 'webdriver', () => false);
+"#;
+
+const LANGUAGES_SCRIPT: &str = r#"
+                const languages = Object.freeze(__LANGUAGES__);
+                defineGetter(Navigator.prototype, 'language', () => languages[0]);
+                defineGetter(Navigator.prototype, 'languages', () => languages);
+"#;
+
+/// Headless reports notifications as denied while `Notification.permission`
+/// says default
+const PERMISSIONS_SCRIPT: &str = r#"
+                if (typeof Permissions !== 'undefined' && typeof Notification !== 'undefined'
+                    && typeof PermissionStatus !== 'undefined') {
+                    const states = new WeakMap();
+                    const desc = Object.getOwnPropertyDescriptor(PermissionStatus.prototype, 'state');
+                    defineGetter(PermissionStatus.prototype, 'state', function() {
+                        if (states.has(this)) return states.get(this);
+                        return desc.get.call(this);
+                    });
+                    const query = Permissions.prototype.query;
+                    Permissions.prototype.query = native(function(parameters) {
+                        const result = query.apply(this, arguments);
+                        if (!parameters || parameters.name !== 'notifications') return result;
+                        const state = Notification.permission === 'default' ? 'prompt' : Notification.permission;
+                        return result.then((status) => {
+                            states.set(status, state);
+                            return status;
+                        });
+                    }, query);
+                }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evasions::fixture;
+    use crate::profiles::ChaserProfile;
+
+    #[test]
+    fn platform() {
+        assert!(Platform
+            .script(&fixture())
+            .contains("'platform', () => \"Win32\");"));
+    }
+
+    #[test]
+    fn hardware() {
+        let script = Hardware.script(&fixture());
+        assert!(script.contains("'hardwareConcurrency', () => 12);"));
+        assert!(script.contains("'deviceMemory', () => 16);"));
+        assert!(script.contains("'maxTouchPoints', () => 0);"));
+    }
+
+    #[test]
+    fn client_hints() {
+        let script = ClientHints.script(&fixture());
+        assert!(script.contains(r#""uaFullVersion":"130.0.6723.116""#));
+        assert!(script.contains(r#""platform":"Windows""#));
+        let ios = ClientHints.script(&ChaserProfile::ios().build());
+        assert!(ios.contains("const uaData = null;"));
+    }
+
+    #[test]
+    fn webdriver() {
+        assert!(WebDriver.script(&fixture()).contains("'webdriver'"));
+    }
+
+    #[test]
+    fn languages() {
+        assert!(Languages
+            .script(&fixture())
+            .contains(r#"Object.freeze(["de-DE","de","en-US","en"])"#));
+    }
+
+    #[test]
+    fn permissions() {
+        assert!(Permissions
+            .script(&fixture())
+            .contains("parameters.name !== 'notifications'"));
+        assert!(!Permissions
+            .script(&fixture())
+            .contains("defineProperty(status"));
+    }
+}
@@ -0,0 +1,229 @@
+//! `navigator.plugins` and `navigator.mimeTypes`.
+//!
+//! Old headless Chrome lists no plugins at all. Desktop Chrome lists the
+//! builtin PDF viewer, since Chrome 94 as the five hardcoded plugins of the
+//! HTML spec, mobile Chrome lists nothing.
+
+use serde_json::{json, Value};
+
+use super::Evasion;
+use crate::stealth::StealthProfile;
+
+/// The first Chrome version reporting the hardcoded PDF viewer plugins and
+/// `navigator.pdfViewerEnabled`
+const HARDCODED_PLUGINS_VERSION: u32 = 94;
+
+/// Builds a cross-linked `PluginArray`/`MimeTypeArray` graph for the
+/// profile's Chrome version: every `Plugin` lists real `MimeType`s, and every
+/// `MimeType` points back to its plugin through `enabledPlugin`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Plugins;
+
+impl Evasion for Plugins {
+    fn name(&self) -> &str {
+        "plugins"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        PLUGINS_SCRIPT.replace("__PLUGINS__", &plugin_data(profile).to_string())
+    }
+}
+
+/// The Chrome major version in the User-Agent, `None` for other browsers
+fn chrome_version(user_agent: &str) -> Option<u32> {
+    let version = user_agent.split("Chrome/").nth(1)?;
+    version.split('.').next()?.parse().ok()
+}
+
+/// The plugins and mime types as `{ plugins, mimeTypes, pdfViewerEnabled }`,
+/// plugins reference their mime types by index and vice versa
+fn plugin_data(profile: &dyn StealthProfile) -> Value {
+    let version = chrome_version(profile.user_agent());
+    if profile.mobile_viewport().is_some() || version.is_none() {
+        return json!({ "plugins": [], "mimeTypes": [], "pdfViewerEnabled": false });
+    }
+
+    if version >= Some(HARDCODED_PLUGINS_VERSION) {
+        let pdf = "Portable Document Format";
+        let plugins = [
+            "PDF Viewer",
+            "Chrome PDF Viewer",
+            "Chromium PDF Viewer",
+            "Microsoft Edge PDF Viewer",
+            "WebKit built-in PDF",
+        ]
+        .map(|name| {
+            json!({
+                "name": name,
+                "filename": "internal-pdf-viewer",
+                "description": pdf,
+                "mimeTypes": [0, 1],
+            })
+        });
+        // every plugin shares the two mime types, enabled by the first one
+        let mime_types = ["application/pdf", "text/pdf"]
+            .map(|ty| json!({ "type": ty, "suffixes": "pdf", "description": pdf, "plugin": 0 }));
+        return json!({ "plugins": plugins, "mimeTypes": mime_types, "pdfViewerEnabled": true });
+    }
+
+    json!({
+        "plugins": [
+            {
+                "name": "Chrome PDF Plugin",
+                "filename": "internal-pdf-viewer",
+                "description": "Portable Document Format",
+                "mimeTypes": [0],
+            },
+            {
+                "name": "Chrome PDF Viewer",
+                "filename": "mhjfbmdgcfjbbpaeojofohoefgiehjai",
+                "description": "",
+                "mimeTypes": [1],
+            },
+            {
+                "name": "Native Client",
+                "filename": "internal-nacl-plugin",
+                "description": "",
+                "mimeTypes": [2, 3],
+            },
+        ],
+        "mimeTypes": [
+            {
+                "type": "application/x-google-chrome-pdf",
+                "suffixes": "pdf",
+                "description": "Portable Document Format",
+                "plugin": 0,
+            },
+            { "type": "application/pdf", "suffixes": "pdf", "description": "", "plugin": 1 },
+            {
+                "type": "application/x-nacl",
+                "suffixes": "",
+                "description": "Native Client Executable",
+                "plugin": 2,
+            },
+            {
+                "type": "application/x-pnacl",
+                "suffixes": "",
+                "description": "Portable Native Client Executable",
+                "plugin": 2,
+            },
+        ],
+        "pdfViewerEnabled": null,
+    })
+}
+
+/// The fake objects are created from the real prototypes and keep their
+/// state in a `WeakMap`, the patched prototype accessors and methods fall back
+/// to the originals for any other object. Indexed entries are enumerable own
+/// properties, named entries non-enumerable ones, like on platform objects.
+const PLUGINS_SCRIPT: &str = r#"
+                const pluginData = __PLUGINS__;
+                if (!inWorker && typeof PluginArray !== 'undefined' && typeof MimeTypeArray !== 'undefined') {
+                    const internals = new WeakMap();
+                    const patchGetters = (proto, props) => {
+                        for (const prop of props) {
+                            const desc = Object.getOwnPropertyDescriptor(proto, prop);
+                            if (!desc || !desc.get) continue;
+                            defineGetter(proto, prop, function() {
+                                return internals.has(this) ? internals.get(this)[prop] : desc.get.call(this);
+                            });
+                        }
+                    };
+                    const patchMethods = (proto, methods) => {
+                        for (const name of Object.keys(methods)) {
+                            const original = proto[name];
+                            if (typeof original !== 'function') continue;
+                            proto[name] = native(function(...args) {
+                                if (!internals.has(this)) return Reflect.apply(original, this, args);
+                                if (args.length < original.length) {
+                                    throw new TypeError("Failed to execute '" + name + "' on '" + proto[Symbol.toStringTag]
+                                        + "': 1 argument required, but only 0 present.");
+                                }
+                                return methods[name](internals.get(this), ...args);
+                            }, original);
+                        }
+                    };
+                    const listMethods = {
+                        item: (state, index) => state.items[index >>> 0] || null,
+                        namedItem: (state, name) => state.named.get(String(name)) || null
+                    };
+
+                    const createList = (proto, items, nameOf) => {
+                        const list = Object.create(proto);
+                        const named = new Map();
+                        items.forEach((item, i) => {
+                            Object.defineProperty(list, i, { value: item, enumerable: true, configurable: true });
+                            if (!named.has(nameOf(item))) named.set(nameOf(item), item);
+                        });
+                        for (const [name, item] of named) {
+                            if (name in list) continue;
+                            Object.defineProperty(list, name, { value: item, enumerable: false, configurable: true });
+                        }
+                        internals.set(list, { length: items.length, items, named });
+                        return list;
+                    };
+
+                    const mimeTypes = pluginData.mimeTypes.map((data) => {
+                        const mimeType = Object.create(MimeType.prototype);
+                        internals.set(mimeType, {
+                            type: data.type,
+                            suffixes: data.suffixes,
+                            description: data.description,
+                            enabledPlugin: null
+                        });
+                        return mimeType;
+                    });
+                    const typeOf = (mimeType) => internals.get(mimeType).type;
+                    const plugins = pluginData.plugins.map((data) => {
+                        const plugin = createList(Plugin.prototype, data.mimeTypes.map((i) => mimeTypes[i]), typeOf);
+                        Object.assign(internals.get(plugin), {
+                            name: data.name,
+                            filename: data.filename,
+                            description: data.description
+                        });
+                        return plugin;
+                    });
+                    pluginData.mimeTypes.forEach((data, i) => {
+                        internals.get(mimeTypes[i]).enabledPlugin = plugins[data.plugin];
+                    });
+                    const pluginArray = createList(PluginArray.prototype, plugins, (plugin) => internals.get(plugin).name);
+                    const mimeTypeArray = createList(MimeTypeArray.prototype, mimeTypes, typeOf);
+
+                    patchGetters(PluginArray.prototype, ['length']);
+                    patchGetters(Plugin.prototype, ['name', 'filename', 'description', 'length']);
+                    patchGetters(MimeTypeArray.prototype, ['length']);
+                    patchGetters(MimeType.prototype, ['type', 'suffixes', 'description', 'enabledPlugin']);
+                    patchMethods(PluginArray.prototype, Object.assign({ refresh: () => undefined }, listMethods));
+                    patchMethods(Plugin.prototype, listMethods);
+                    patchMethods(MimeTypeArray.prototype, listMethods);
+
+                    defineGetter(Navigator.prototype, 'plugins', () => pluginArray);
+                    defineGetter(Navigator.prototype, 'mimeTypes', () => mimeTypeArray);
+                    if (pluginData.pdfViewerEnabled !== null && 'pdfViewerEnabled' in Navigator.prototype) {
+                        defineGetter(Navigator.prototype, 'pdfViewerEnabled', () => pluginData.pdfViewerEnabled);
+                    }
+                }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evasions::fixture;
+    use crate::profiles::ChaserProfile;
+
+    #[test]
+    fn plugins() {
+        let data = plugin_data(&fixture());
+        assert_eq!(data["plugins"].as_array().unwrap().len(), 5);
+        assert_eq!(data["mimeTypes"].as_array().unwrap().len(), 2);
+        assert_eq!(data["plugins"][4]["name"], "WebKit built-in PDF");
+        assert_eq!(data["mimeTypes"][1]["plugin"], 0);
+
+        let legacy = plugin_data(&ChaserProfile::windows().chrome_version(90).build());
+        assert_eq!(legacy["plugins"][2]["mimeTypes"], json!([2, 3]));
+        assert_eq!(legacy["mimeTypes"].as_array().unwrap().len(), 4);
+
+        let android = Plugins.script(&ChaserProfile::android().build());
+        assert!(android.contains(r#"{"mimeTypes":[],"pdfViewerEnabled":false,"plugins":[]}"#));
+    }
+}
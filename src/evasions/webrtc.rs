@@ -0,0 +1,37 @@
+//! WebRTC candidate filtering, see [`WebRtcPolicy`].
+//!
+//! [`WebRtcPolicy`]: crate::profiles::WebRtcPolicy
+
+use super::Evasion;
+use crate::profiles::webrtc_script;
+use crate::stealth::StealthProfile;
+
+/// Applies [`StealthProfile::webrtc_policy`] to the candidates the page sees
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WebRtc;
+
+impl Evasion for WebRtc {
+    fn name(&self) -> &str {
+        "webrtc"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        webrtc_script(profile.webrtc_policy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evasions::fixture;
+    use crate::profiles::{ChaserProfile, WebRtcPolicy};
+
+    #[test]
+    fn webrtc() {
+        assert!(WebRtc.script(&fixture()).is_empty());
+        let disabled = ChaserProfile::windows()
+            .webrtc_policy(WebRtcPolicy::Disabled)
+            .build();
+        assert!(WebRtc.script(&disabled).contains("delete window[name]"));
+    }
+}
@@ -0,0 +1,170 @@
+//! `window` level objects: the `chrome` global and the screen.
+
+use serde_json::Value;
+
+use super::Evasion;
+use crate::stealth::StealthProfile;
+
+/// Provides the `window.chrome` object with `runtime`, `csi`, `loadTimes`
+/// and `app`, which headless Chrome lacks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChromeObject;
+
+impl Evasion for ChromeObject {
+    fn name(&self) -> &str {
+        "chrome"
+    }
+
+    fn script(&self, _profile: &dyn StealthProfile) -> String {
+        CHROME_SCRIPT.to_string()
+    }
+}
+
+/// Reports the profile's screen size, see [`StealthProfile::screen_size`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Screen;
+
+impl Evasion for Screen {
+    fn name(&self) -> &str {
+        "screen"
+    }
+
+    fn script(&self, profile: &dyn StealthProfile) -> String {
+        let Some((width, height)) = profile.screen_size() else {
+            return String::new();
+        };
+        let (avail_width, avail_height) = profile.screen_avail_size().unwrap_or((width, height));
+        let values = serde_json::json!({
+            "width": width,
+            "height": height,
+            "availWidth": avail_width,
+            "availHeight": avail_height,
+            "availLeft": 0,
+            "availTop": 0,
+            "colorDepth": 24,
+            "pixelDepth": 24,
+        });
+        SCREEN_SCRIPT.replace("__SCREEN__", &Value::to_string(&values))
+    }
+}
+
+const CHROME_SCRIPT: &str = r#"
+                if (!window.chrome) {
+                    window.chrome = {};
+                }
+                if (!window.chrome.runtime) {
+                    window.chrome.runtime = {};
+                }
+
+                // Chrome Runtime APIs (required by Turnstile)
+                if (!window.chrome.runtime.connect) {
+                    const portEvent = () => ({
+                        addListener: native(function(callback) {}, 'addListener', 1),
+                        removeListener: native(function(callback) {}, 'removeListener', 1),
+                        hasListener: native(function(callback) { return false; }, 'hasListener', 1),
+                        hasListeners: native(function() { return false; }, 'hasListeners', 0)
+                    });
+                    window.chrome.runtime.connect = native(function() {
+                        return {
+                            name: '',
+                            sender: undefined,
+                            onDisconnect: portEvent(),
+                            onMessage: portEvent(),
+                            postMessage: native(function(message) {}, 'postMessage', 1),
+                            disconnect: native(function() {}, 'disconnect', 0)
+                        };
+                    }, 'connect', 0);
+                }
+                if (!window.chrome.runtime.sendMessage) {
+                    window.chrome.runtime.sendMessage = native(function() { return; }, 'sendMessage', 0);
+                }
+
+                // Chrome CSI (Chrome Speed Index) - some sites check this
+                if (!window.chrome.csi) {
+                    window.chrome.csi = native(function() {
+                        const now = Date.now();
+                        return { 
+                            startE: now, 
+                            onloadT: now, 
+                            pageT: now, 
+                            tran: 15 
+                        };
+                    }, '', 0);
+                }
+
+                // Chrome loadTimes (deprecated but still checked)
+                if (!window.chrome.loadTimes) {
+                    window.chrome.loadTimes = native(function() {
+                        const now = Date.now() / 1000;
+                        return {
+                            requestTime: now,
+                            startLoadTime: now,
+                            commitLoadTime: now,
+                            finishDocumentLoadTime: now,
+                            finishLoadTime: now,
+                            firstPaintTime: now,
+                            firstPaintAfterLoadTime: 0,
+                            navigationType: "Other",
+                            wasFetchedViaSpdy: false,
+                            wasNpnNegotiated: false,
+                            npnNegotiatedProtocol: "",
+                            wasAlternateProtocolAvailable: false,
+                            connectionInfo: "http/1.1"
+                        };
+                    }, '', 0);
+                }
+
+                // Chrome app object
+                if (!window.chrome.app) {
+                    window.chrome.app = {
+                        isInstalled: false,
+                        InstallState: { 
+                            DISABLED: 'disabled', 
+                            INSTALLED: 'installed', 
+                            NOT_INSTALLED: 'not_installed' 
+                        },
+                        RunningState: { 
+                            CANNOT_RUN: 'cannot_run', 
+                            READY_TO_RUN: 'ready_to_run', 
+                            RUNNING: 'running' 
+                        },
+                        getDetails: native(function() { return null; }, 'getDetails', 0),
+                        getIsInstalled: native(function() { return false; }, 'getIsInstalled', 0)
+                    };
+                }
+"#;
+
+const SCREEN_SCRIPT: &str = r#"
+                const screenValues = __SCREEN__;
+                for (const [key, value] of Object.entries(screenValues)) {
+                    defineGetter(Screen.prototype, key, () => value);
+                }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evasions::fixture;
+    use crate::profiles::LinuxProfile;
+
+    #[test]
+    fn chrome_object() {
+        let script = ChromeObject.script(&fixture());
+        assert!(script.contains("window.chrome.runtime.connect"));
+        assert!(script.contains("window.chrome.loadTimes"));
+        for (index, _) in script.match_indices("function(") {
+            assert!(
+                script[..index].ends_with("native("),
+                "unwrapped function at {index}"
+            );
+        }
+    }
+
+    #[test]
+    fn screen() {
+        let script = Screen.script(&fixture());
+        assert!(script.contains(r#""width":2560"#));
+        assert!(script.contains(r#""availHeight":1400"#));
+        assert!(Screen.script(&LinuxProfile).is_empty());
+    }
+}
@@ -1,13 +1,23 @@
 use chromiumoxide_cdp::cdp::browser_protocol::emulation::{
     ScreenOrientation, ScreenOrientationType, SetDeviceMetricsOverrideParams,
-    SetTouchEmulationEnabledParams,
+    SetLocaleOverrideParams, SetTimezoneOverrideParams, SetTouchEmulationEnabledParams,
 };
-use chromiumoxide_types::Method;
+use chromiumoxide_cdp::cdp::browser_protocol::network::SetUserAgentOverrideParams;
+use chromiumoxide_cdp::cdp::browser_protocol::page::AddScriptToEvaluateOnNewDocumentParams;
+use chromiumoxide_cdp::cdp::js_protocol::runtime::EvaluateParams;
+use chromiumoxide_types::{Method, MethodId};
 
 use crate::cmd::CommandChain;
+use crate::error::{CdpError, Result};
+use crate::handler::target::TargetType;
 use crate::handler::viewport::Viewport;
+use crate::stealth::StealthProfile;
 use std::time::Duration;
 
+/// The number of touch points reported by emulated touch screens, which
+/// matches current phones
+pub const MAX_TOUCH_POINTS: i64 = 5;
+
 #[derive(Debug)]
 pub struct EmulationManager {
     pub emulating_mobile: bool,
@@ -26,7 +36,13 @@ impl EmulationManager {
         }
     }
 
-    pub fn init_commands(&mut self, viewport: &Viewport) -> CommandChain {
+    /// Returns the device metrics and touch emulation overrides for `viewport`
+    pub fn viewport_commands(
+        viewport: &Viewport,
+    ) -> (
+        SetDeviceMetricsOverrideParams,
+        SetTouchEmulationEnabledParams,
+    ) {
         let orientation = if viewport.is_landscape {
             ScreenOrientation::new(ScreenOrientationType::LandscapePrimary, 90)
         } else {
@@ -42,7 +58,16 @@ impl EmulationManager {
             .build()
             .unwrap();
 
-        let set_touch = SetTouchEmulationEnabledParams::new(true);
+        let mut set_touch = SetTouchEmulationEnabledParams::new(viewport.has_touch);
+        if viewport.has_touch {
+            set_touch.max_touch_points = Some(MAX_TOUCH_POINTS);
+        }
+
+        (set_device, set_touch)
+    }
+
+    pub fn init_commands(&mut self, viewport: &Viewport) -> CommandChain {
+        let (set_device, set_touch) = Self::viewport_commands(viewport);
 
         let chain = CommandChain::new(
             vec![
@@ -60,6 +85,151 @@ impl EmulationManager {
 
         self.needs_reload = self.emulating_mobile != viewport.emulating_mobile
             || self.has_touch != viewport.has_touch;
+        self.emulating_mobile = viewport.emulating_mobile;
+        self.has_touch = viewport.has_touch;
         chain
     }
 }
+
+/// The commands that apply a [`StealthProfile`] to a session.
+///
+/// Pages run them through their own session, the handler sends them to
+/// popups, iframes and workers while those are paused at start.
+#[derive(Debug, Clone)]
+pub struct ProfileCommands {
+    pub(crate) user_agent: SetUserAgentOverrideParams,
+    pub(crate) timezone: Option<SetTimezoneOverrideParams>,
+    pub(crate) locale: Option<SetLocaleOverrideParams>,
+    pub(crate) device_metrics: Option<SetDeviceMetricsOverrideParams>,
+    pub(crate) touch: Option<SetTouchEmulationEnabledParams>,
+    /// Injected into every new document and evaluated in workers
+    pub(crate) script: String,
+}
+
+impl ProfileCommands {
+    pub(crate) fn new(profile: &dyn StealthProfile, script: String) -> Result<Self> {
+        let mut user_agent = SetUserAgentOverrideParams::builder()
+            .user_agent(profile.user_agent())
+            .accept_language(profile.accept_language())
+            .platform(profile.platform());
+        if let Some(metadata) = profile.user_agent_metadata() {
+            user_agent = user_agent.user_agent_metadata(metadata);
+        }
+
+        let (device_metrics, touch) = if let Some(viewport) = profile.mobile_viewport() {
+            // Mobile profiles take over the whole viewport and enable touch
+            let (set_device, set_touch) = EmulationManager::viewport_commands(&viewport);
+            (Some(set_device), Some(set_touch))
+        } else if let Some((width, height)) = profile.screen_size() {
+            // A zero width/height keeps the current window metrics and only
+            // overrides what `screen` and `devicePixelRatio` report
+            let set_device = SetDeviceMetricsOverrideParams::builder()
+                .width(0)
+                .height(0)
+                .device_scale_factor(profile.device_scale_factor())
+                .mobile(false)
+                .screen_width(width)
+                .screen_height(height)
+                .build()
+                .map_err(CdpError::msg)?;
+            (Some(set_device), None)
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            user_agent: user_agent.build().map_err(CdpError::msg)?,
+            timezone: profile.timezone().map(SetTimezoneOverrideParams::new),
+            locale: profile
+                .locale()
+                .map(|locale| SetLocaleOverrideParams::builder().locale(locale).build()),
+            device_metrics,
+            touch,
+            script,
+        })
+    }
+
+    /// Drops the `Accept-Language`, timezone, locale and screen overrides,
+    /// keeping the user agent, client hints and script
+    pub(crate) fn without_emulation(mut self) -> Self {
+        self.user_agent.accept_language = None;
+        self.timezone = None;
+        self.locale = None;
+        self.device_metrics = None;
+        self.touch = None;
+        self
+    }
+
+    /// The commands for a session of a target of type `ty` that is paused at
+    /// start, empty if the target doesn't run scripts
+    pub(crate) fn commands_for(&self, ty: &TargetType) -> Vec<(MethodId, serde_json::Value)> {
+        if ty.is_page() || ty.is_iframe() {
+            self.page_commands()
+        } else if ty.is_any_worker() {
+            // workers report the user agent of the browser unless their own
+            // session overrides it
+            vec![
+                to_command(&self.user_agent),
+                to_command(&EvaluateParams::new(self.script.clone())),
+            ]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The commands for a page or iframe session, in order
+    fn page_commands(&self) -> Vec<(MethodId, serde_json::Value)> {
+        let mut cmds = vec![to_command(&self.user_agent)];
+        cmds.extend(self.timezone.as_ref().map(to_command));
+        cmds.extend(self.locale.as_ref().map(to_command));
+        cmds.extend(self.device_metrics.as_ref().map(to_command));
+        cmds.extend(self.touch.as_ref().map(to_command));
+        cmds.push(to_command(&self.add_script()));
+        cmds
+    }
+
+    pub(crate) fn add_script(&self) -> AddScriptToEvaluateOnNewDocumentParams {
+        AddScriptToEvaluateOnNewDocumentParams::new(self.script.clone())
+    }
+}
+
+fn to_command<T: Method + serde::Serialize>(cmd: &T) -> (MethodId, serde_json::Value) {
+    (cmd.identifier(), serde_json::to_value(cmd).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_commands_follow_the_target_type() {
+        let profile = crate::evasions::fixture();
+        let commands = ProfileCommands::new(&profile, "/* script */".to_string()).unwrap();
+
+        let page = commands.commands_for(&TargetType::Iframe);
+        assert_eq!(page.first().unwrap().0, "Network.setUserAgentOverride");
+        assert_eq!(
+            page.last().unwrap().0,
+            "Page.addScriptToEvaluateOnNewDocument"
+        );
+        assert!(page
+            .iter()
+            .any(|(method, _)| method == "Emulation.setTimezoneOverride"));
+
+        for ty in [TargetType::Worker, TargetType::SharedWorker] {
+            let worker = commands.commands_for(&ty);
+            assert_eq!(worker.len(), 2);
+            assert_eq!(worker[0].0, "Network.setUserAgentOverride");
+            assert_eq!(worker[0].1["userAgent"], profile.user_agent());
+            assert!(worker[0].1.get("userAgentMetadata").is_some());
+            assert_eq!(worker[1].0, "Runtime.evaluate");
+            assert_eq!(worker[1].1["expression"], "/* script */");
+        }
+
+        assert!(commands.commands_for(&TargetType::Browser).is_empty());
+
+        let page = commands.without_emulation().commands_for(&TargetType::Page);
+        assert_eq!(page.len(), 2);
+        assert!(page[0].1.get("acceptLanguage").is_none());
+    }
+}
@@ -1,4 +1,6 @@
-use chromiumoxide_cdp::cdp::js_protocol::runtime::ExecutionContextId;
+use chromiumoxide_cdp::cdp::js_protocol::runtime::{
+    ExecutionContextId, RunIfWaitingForDebuggerParams,
+};
 use dashmap::DashMap;
 use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
@@ -20,16 +22,19 @@ use chromiumoxide_types::{CallId, Message, Method, Response};
 use chromiumoxide_types::{MethodId, Request as CdpRequest};
 pub(crate) use page::PageInner;
 
+use crate::auth::Credentials;
 use crate::cmd::{to_command_response, CommandMessage};
 use crate::conn::Connection;
 use crate::error::{CdpError, Result};
 use crate::handler::browser::BrowserContext;
+use crate::handler::emulation::ProfileCommands;
 use crate::handler::frame::FrameNavigationRequest;
 use crate::handler::frame::{NavigationError, NavigationId, NavigationOk};
 use crate::handler::job::PeriodicJob;
+use crate::handler::network::NetworkManager;
 use crate::handler::session::Session;
 use crate::handler::target::TargetEvent;
-use crate::handler::target::{Target, TargetConfig};
+use crate::handler::target::{Target, TargetConfig, TargetType};
 use crate::handler::viewport::Viewport;
 use crate::page::Page;
 
@@ -88,6 +93,10 @@ pub struct Handler {
     closing: bool,
     /// Stealth Context Registry
     contexts: Arc<DashMap<TargetId, ExecutionContextId>>,
+    /// The profile pages start with unless their opener has one
+    default_profile: Option<Arc<ProfileCommands>>,
+    /// The credentials every new page answers auth challenges with
+    default_credentials: Option<Credentials>,
 }
 
 impl Handler {
@@ -105,6 +114,22 @@ impl Handler {
             serde_json::to_value(discover).unwrap(),
         );
 
+        if config.default_profile.is_some() {
+            // pause new targets until the handler applied the profile, see
+            // `Browser::set_default_profile`
+            let auto_attach = SetAutoAttachParams::builder()
+                .auto_attach(true)
+                .wait_for_debugger_on_start(true)
+                .flatten(true)
+                .build()
+                .unwrap();
+            let _ = conn.submit_command(
+                auto_attach.identifier(),
+                None,
+                serde_json::to_value(auto_attach).unwrap(),
+            );
+        }
+
         let browser_contexts = config
             .context_ids
             .iter()
@@ -123,10 +148,12 @@ impl Handler {
             conn,
             evict_command_timeout: PeriodicJob::new(config.request_timeout),
             next_navigation_id: 0,
-            config,
             event_listeners: Default::default(),
             closing: false,
             contexts: Arc::new(DashMap::new()),
+            default_profile: config.default_profile.clone(),
+            default_credentials: None,
+            config,
         }
     }
 
@@ -450,6 +477,11 @@ impl Handler {
             .map(BrowserContext::from)
             .filter(|id| self.browser_contexts.contains(id))
             .unwrap_or_else(|| self.default_browser_context.clone());
+        let profile = profile_for(
+            &self.targets,
+            &event.target_info,
+            self.default_profile.as_ref(),
+        );
         let target = Target::new(
             event.target_info,
             TargetConfig {
@@ -458,6 +490,8 @@ impl Handler {
                 viewport: self.config.viewport.clone(),
                 request_intercept: self.config.request_intercept,
                 cache_enabled: self.config.cache_enabled,
+                profile,
+                credentials: self.default_credentials.clone(),
             },
             browser_ctx,
         );
@@ -466,9 +500,61 @@ impl Handler {
     }
 
     /// A new session is attached to a target
+    ///
+    /// Targets the browser auto-attached to are paused until the profile of
+    /// the target has been applied.
     fn on_attached_to_target(&mut self, event: Box<EventAttachedToTarget>) {
-        let session = Session::new(event.session_id.clone(), event.target_info.target_id);
+        let session = Session::new(
+            event.session_id.clone(),
+            event.target_info.target_id.clone(),
+        );
+
+        if event.waiting_for_debugger {
+            let ty = TargetType::new(&event.target_info.r#type);
+            let mut cmds = profile_for(
+                &self.targets,
+                &event.target_info,
+                self.default_profile.as_ref(),
+            )
+            .map(|profile| profile.commands_for(&ty))
+            .unwrap_or_default();
+            // popups and OOPIFs can attach before their `TargetCreated`
+            // arrives, those start with the defaults
+            let credentials = match self.targets.get(session.target_id()) {
+                Some(target) => target.credentials().is_some(),
+                None => self.default_credentials.is_some(),
+            };
+            if ty.is_page() && credentials {
+                // the first request of the page may already hit the proxy
+                let fetch = NetworkManager::interception_params();
+                cmds.push((fetch.identifier(), serde_json::to_value(fetch).unwrap()));
+            }
+            if ty.is_iframe() {
+                cmds.push(Target::auto_attach_command());
+            }
+            let run = RunIfWaitingForDebuggerParams::default();
+            cmds.push((run.identifier(), serde_json::to_value(run).unwrap()));
+            for (method, params) in cmds {
+                let _ = self
+                    .conn
+                    .submit_command(method, Some(event.session_id.clone()), params);
+            }
+        }
+
         if let Some(target) = self.targets.get_mut(session.target_id()) {
+            if target.session_id().is_some() {
+                // the browser also attaches to targets that are already
+                // attached once auto-attach is turned on
+                let detach = DetachFromTargetParams::builder()
+                    .session_id(event.session_id)
+                    .build();
+                let _ = self.conn.submit_command(
+                    detach.identifier(),
+                    None,
+                    serde_json::to_value(detach).unwrap(),
+                );
+                return;
+            }
             target.set_session_id(session.session_id().clone())
         }
         self.sessions.insert(event.session_id, session);
@@ -598,6 +684,12 @@ impl Stream for Handler {
                     HandlerMessage::AddEventListener(req) => {
                         pin.event_listeners.add_listener(req);
                     }
+                    HandlerMessage::SetDefaultProfile(profile) => {
+                        pin.default_profile = Some(profile);
+                    }
+                    HandlerMessage::SetDefaultCredentials(credentials) => {
+                        pin.default_credentials = Some(credentials);
+                    }
                 }
             }
 
@@ -677,6 +769,24 @@ impl Stream for Handler {
     }
 }
 
+/// The profile of a target: the one it was created with, otherwise popups
+/// inherit the profile of the page that opened them and everything else
+/// starts with the default profile
+fn profile_for(
+    targets: &HashMap<TargetId, Target>,
+    info: &TargetInfo,
+    default_profile: Option<&Arc<ProfileCommands>>,
+) -> Option<Arc<ProfileCommands>> {
+    if let Some(target) = targets.get(&info.target_id) {
+        return target.profile().cloned();
+    }
+    info.opener_id
+        .as_ref()
+        .and_then(|id| targets.get(id))
+        .and_then(|opener| opener.profile().cloned())
+        .or_else(|| default_profile.cloned())
+}
+
 /// How to configure the handler
 #[derive(Debug, Clone)]
 pub struct HandlerConfig {
@@ -694,6 +804,9 @@ pub struct HandlerConfig {
     pub request_intercept: bool,
     /// Whether to enable cache
     pub cache_enabled: bool,
+    /// The profile applied to every new target, see
+    /// [`Browser::set_default_profile`](crate::Browser::set_default_profile)
+    pub default_profile: Option<Arc<ProfileCommands>>,
 }
 
 impl Default for HandlerConfig {
@@ -706,6 +819,7 @@ impl Default for HandlerConfig {
             request_timeout: Duration::from_millis(REQUEST_TIMEOUT),
             request_intercept: false,
             cache_enabled: true,
+            default_profile: None,
         }
     }
 }
@@ -788,4 +902,59 @@ pub(crate) enum HandlerMessage {
     GetPage(TargetId, OneshotSender<Option<Page>>),
     AddEventListener(EventListenerRequest),
     CloseBrowser(OneshotSender<Result<CloseReturns>>),
+    SetDefaultProfile(Arc<ProfileCommands>),
+    SetDefaultCredentials(Credentials),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target_info(id: &str, opener: Option<&str>) -> TargetInfo {
+        let mut builder = TargetInfo::builder()
+            .target_id(id.to_string())
+            .r#type("page")
+            .title("")
+            .url("about:blank")
+            .attached(false)
+            .can_access_opener(false);
+        if let Some(opener) = opener {
+            builder = builder.opener_id(opener.to_string());
+        }
+        builder.build().unwrap()
+    }
+
+    fn register(targets: &mut HashMap<TargetId, Target>, id: &str, profile: &Arc<ProfileCommands>) {
+        let config = TargetConfig {
+            profile: Some(profile.clone()),
+            ..Default::default()
+        };
+        let target = Target::new(target_info(id, None), config, BrowserContext::default());
+        targets.insert(target.target_id().clone(), target);
+    }
+
+    #[test]
+    fn unregistered_targets_start_with_a_profile() {
+        let profile = crate::evasions::fixture();
+        let default = Arc::new(ProfileCommands::new(&profile, String::new()).unwrap());
+        let opener = Arc::new(ProfileCommands::new(&profile, "/* opener */".into()).unwrap());
+        let own = Arc::new(ProfileCommands::new(&profile, "/* own */".into()).unwrap());
+        let mut targets = HashMap::new();
+        register(&mut targets, "opener", &opener);
+        register(&mut targets, "page", &own);
+
+        // attached before `TargetCreated` was processed
+        let popup = profile_for(
+            &targets,
+            &target_info("popup", Some("opener")),
+            Some(&default),
+        );
+        assert!(Arc::ptr_eq(&popup.unwrap(), &opener));
+        let oopif = profile_for(&targets, &target_info("oopif", None), Some(&default));
+        assert!(Arc::ptr_eq(&oopif.unwrap(), &default));
+        assert!(profile_for(&targets, &target_info("oopif", None), None).is_none());
+
+        let page = profile_for(&targets, &target_info("page", None), Some(&default));
+        assert!(Arc::ptr_eq(&page.unwrap(), &own));
+    }
 }
@@ -113,6 +113,14 @@ impl NetworkManager {
         self.update_protocol_request_interception()
     }
 
+    /// Pauses every request and answers auth challenges
+    pub(crate) fn interception_params() -> fetch::EnableParams {
+        fetch::EnableParams::builder()
+            .handle_auth_requests(true)
+            .pattern(RequestPattern::builder().url_pattern("*").build())
+            .build()
+    }
+
     fn update_protocol_request_interception(&mut self) {
         let enabled = self.user_request_interception_enabled || self.credentials.is_some();
         if enabled == self.protocol_request_interception_enabled {
@@ -121,12 +129,7 @@ impl NetworkManager {
         self.update_protocol_cache_disabled();
         self.protocol_request_interception_enabled = enabled;
         if enabled {
-            self.push_cdp_request(
-                fetch::EnableParams::builder()
-                    .handle_auth_requests(true)
-                    .pattern(RequestPattern::builder().url_pattern("*").build())
-                    .build(),
-            )
+            self.push_cdp_request(Self::interception_params())
         } else {
             self.push_cdp_request(DisableParams::default())
         }
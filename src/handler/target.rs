@@ -16,7 +16,7 @@ use chromiumoxide_cdp::cdp::browser_protocol::{
 };
 use chromiumoxide_cdp::cdp::events::CdpEvent;
 use chromiumoxide_cdp::cdp::CdpEventMessage;
-use chromiumoxide_types::{Command, Method, Request, Response};
+use chromiumoxide_types::{Command, Method, MethodId, Request, Response};
 
 use crate::auth::Credentials;
 use crate::cdp::browser_protocol::target::CloseTargetParams;
@@ -25,7 +25,7 @@ use crate::cmd::CommandMessage;
 use crate::error::{CdpError, Result};
 use crate::handler::browser::BrowserContext;
 use crate::handler::domworld::DOMWorldKind;
-use crate::handler::emulation::EmulationManager;
+use crate::handler::emulation::{EmulationManager, ProfileCommands};
 use crate::handler::frame::{
     FrameEvent, FrameManager, NavigationError, NavigationId, NavigationOk,
 };
@@ -92,6 +92,8 @@ pub struct Target {
     wait_for_frame_navigation: Vec<Sender<ArcHttpRequest>>,
     /// The sender who requested the page.
     initiator: Option<Sender<Result<Page>>>,
+    /// The profile applied to this target's iframes and workers
+    profile: Option<Arc<ProfileCommands>>,
 }
 
 impl Target {
@@ -99,11 +101,15 @@ impl Target {
     /// `CreateTargetParams` request.
     pub fn new(info: TargetInfo, config: TargetConfig, browser_context: BrowserContext) -> Self {
         let ty = TargetType::new(&info.r#type);
+        let profile = config.profile.clone();
         let request_timeout = config.request_timeout;
         let mut network_manager = NetworkManager::new(config.ignore_https_errors, request_timeout);
 
         network_manager.set_cache_enabled(config.cache_enabled);
         network_manager.set_request_interception(config.request_intercept);
+        if let Some(credentials) = config.credentials.clone() {
+            network_manager.authenticate(credentials);
+        }
 
         Self {
             info,
@@ -119,6 +125,7 @@ impl Target {
             queued_events: Default::default(),
             event_listeners: Default::default(),
             initiator: None,
+            profile,
             browser_context,
         }
     }
@@ -149,6 +156,17 @@ impl Target {
         &self.r#type
     }
 
+    /// The profile applied to this target's popups, iframes and workers
+    pub(crate) fn profile(&self) -> Option<&Arc<ProfileCommands>> {
+        self.profile.as_ref()
+    }
+
+    /// The credentials this target started with, see
+    /// [`Browser::authenticate`](crate::Browser::authenticate)
+    pub(crate) fn credentials(&self) -> Option<&Credentials> {
+        self.config.credentials.as_ref()
+    }
+
     /// Whether this target is already initialized
     pub fn is_initialized(&self) -> bool {
         matches!(self.init_state, TargetInit::Initialized)
@@ -257,17 +275,41 @@ impl Target {
 
             // `Target` events
             CdpEvent::TargetAttachedToTarget(ev) => {
+                let ty = TargetType::new(&ev.target_info.r#type);
+                let patched = self.profile.is_some() && ty.is_any_worker();
                 if ev.waiting_for_debugger {
+                    // out-of-process iframes and workers are paused before
+                    // their first statement, the profile is applied before
+                    // anything they load runs
+                    let mut cmds = self
+                        .profile
+                        .as_ref()
+                        .map(|profile| profile.commands_for(&ty))
+                        .unwrap_or_default();
+                    if ty.is_iframe() {
+                        // iframe sessions never run the page init commands,
+                        // workers and iframes nested in them need to be
+                        // paused as well
+                        cmds.push(Self::auto_attach_command());
+                    }
                     let runtime_cmd = RunIfWaitingForDebuggerParams::default();
+                    cmds.push((
+                        runtime_cmd.identifier(),
+                        serde_json::to_value(runtime_cmd).unwrap(),
+                    ));
 
-                    self.queued_events.push_back(TargetEvent::Request(Request {
-                        method: runtime_cmd.identifier(),
-                        session_id: Some(ev.session_id.clone().into()),
-                        params: serde_json::to_value(runtime_cmd).unwrap(),
-                    }));
+                    for (method, params) in cmds {
+                        self.queued_events.push_back(TargetEvent::Request(Request {
+                            method,
+                            session_id: Some(ev.session_id.clone().into()),
+                            params,
+                        }));
+                    }
                 }
 
-                if "service_worker" == &ev.target_info.r#type {
+                // stay attached to patched service workers, detaching would
+                // drop the patches
+                if ty.is_service_worker() && !patched {
                     let detach_command = DetachFromTargetParams::builder()
                         .session_id(ev.session_id.clone())
                         .build();
@@ -329,6 +371,10 @@ impl Target {
                 self.init_state = TargetInit::InitializingFrame(FrameManager::init_commands(
                     self.config.request_timeout,
                 ));
+                if self.session_id.is_some() {
+                    // already auto-attached by the browser
+                    return self.poll(cx, now);
+                }
                 let params = AttachToTargetParams::builder()
                     .target_id(self.target_id().clone())
                     .flatten(true)
@@ -518,6 +564,9 @@ impl Target {
                         TargetMessage::Authenticate(credentials) => {
                             self.network_manager.authenticate(credentials);
                         }
+                        TargetMessage::Profile(profile) => {
+                            self.profile = Some(profile);
+                        }
                     }
                 }
             }
@@ -568,18 +617,24 @@ impl Target {
         self.initiator = Some(tx);
     }
 
-    pub(crate) fn page_init_commands(timeout: Duration) -> CommandChain {
+    /// Pauses the iframes and workers a session starts until their profile
+    /// has been applied
+    pub(crate) fn auto_attach_command() -> (MethodId, serde_json::Value) {
         let attach = SetAutoAttachParams::builder()
             .flatten(true)
             .auto_attach(true)
             .wait_for_debugger_on_start(true)
             .build()
             .unwrap();
+        (attach.identifier(), serde_json::to_value(attach).unwrap())
+    }
+
+    pub(crate) fn page_init_commands(timeout: Duration) -> CommandChain {
         let enable_performance = performance::EnableParams::default();
         let enable_log = cdplog::EnableParams::default();
         CommandChain::new(
             vec![
-                (attach.identifier(), serde_json::to_value(attach).unwrap()),
+                Self::auto_attach_command(),
                 (
                     enable_performance.identifier(),
                     serde_json::to_value(enable_performance).unwrap(),
@@ -602,6 +657,10 @@ pub struct TargetConfig {
     pub viewport: Option<Viewport>,
     pub request_intercept: bool,
     pub cache_enabled: bool,
+    /// The profile new pages start with
+    pub profile: Option<Arc<ProfileCommands>>,
+    /// The credentials new pages answer auth challenges with
+    pub credentials: Option<Credentials>,
 }
 
 impl Default for TargetConfig {
@@ -612,6 +671,8 @@ impl Default for TargetConfig {
             viewport: Default::default(),
             request_intercept: false,
             cache_enabled: true,
+            profile: None,
+            credentials: None,
         }
     }
 }
@@ -620,6 +681,8 @@ impl Default for TargetConfig {
 pub enum TargetType {
     Page,
     BackgroundPage,
+    Iframe,
+    Worker,
     ServiceWorker,
     SharedWorker,
     Other,
@@ -633,6 +696,8 @@ impl TargetType {
         match ty {
             "page" => TargetType::Page,
             "background_page" => TargetType::BackgroundPage,
+            "iframe" => TargetType::Iframe,
+            "worker" => TargetType::Worker,
             "service_worker" => TargetType::ServiceWorker,
             "shared_worker" => TargetType::SharedWorker,
             "other" => TargetType::Other,
@@ -650,6 +715,22 @@ impl TargetType {
         matches!(self, TargetType::BackgroundPage)
     }
 
+    pub fn is_iframe(&self) -> bool {
+        matches!(self, TargetType::Iframe)
+    }
+
+    pub fn is_worker(&self) -> bool {
+        matches!(self, TargetType::Worker)
+    }
+
+    /// Whether this is a dedicated, shared or service worker
+    pub fn is_any_worker(&self) -> bool {
+        matches!(
+            self,
+            TargetType::Worker | TargetType::SharedWorker | TargetType::ServiceWorker
+        )
+    }
+
     pub fn is_service_worker(&self) -> bool {
         matches!(self, TargetType::ServiceWorker)
     }
@@ -781,4 +862,7 @@ pub enum TargetMessage {
     /// Get the `ExecutionContext` if available
     GetExecutionContext(GetExecutionContext),
     Authenticate(Credentials),
+    /// Apply this profile to every out-of-process iframe and dedicated,
+    /// shared and service worker the target attaches to, before they run
+    Profile(Arc<ProfileCommands>),
 }
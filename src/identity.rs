@@ -0,0 +1,376 @@
+//! Persistent identities.
+//!
+//! A returning visitor shows the same fingerprint, cookies, `localStorage`
+//! and IndexedDB on every visit. An [`Identity`] bundles a [`ChaserProfile`]
+//! with a dedicated Chrome user data dir, an optional proxy and free-form
+//! metadata, all stored in one directory:
+//!
+//! ```text
+//! identities/alice/
+//! ├── identity.toml   name, proxy, metadata and the profile
+//! └── user-data/      Chrome's user data dir
+//! ```
+//!
+//! ```rust,no_run
+//! use chaser_oxide::identity::Identity;
+//! use chaser_oxide::ChaserProfile;
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let identity = Identity::builder("identities/alice", ChaserProfile::windows().build())
+//!     .proxy("http://203.0.113.7:8080")
+//!     .metadata("team", "growth")
+//!     .build();
+//! identity.save()?;
+//!
+//! // any later run
+//! let identity = Identity::load("identities/alice")?;
+//! let (browser, chaser) = identity.launch().await?;
+//! chaser.goto("https://example.com").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::auth::Credentials;
+use crate::browser::{Browser, BrowserConfig, BrowserConfigBuilder};
+use crate::chaser::ChaserPage;
+use crate::profiles::ChaserProfile;
+use crate::stealth::StealthProfile;
+
+/// The file an identity is stored in, inside its directory
+const IDENTITY_FILE: &str = "identity.toml";
+
+/// The user data dir of an identity that doesn't set its own
+const USER_DATA_DIR: &str = "user-data";
+
+/// Errors that occur while loading or saving an identity
+#[derive(Debug, Error)]
+pub enum IdentityError {
+    #[error("failed to access identity {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("invalid identity {}: {source}", path.display())]
+    Toml {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error("failed to serialize identity {}: {source}", path.display())]
+    Serialize {
+        path: PathBuf,
+        #[source]
+        source: toml::ser::Error,
+    },
+}
+
+/// The proxy an identity connects through
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proxy {
+    /// The `--proxy-server` value, e.g. `http://203.0.113.7:8080` or
+    /// `socks5://203.0.113.7:1080`
+    pub server: String,
+    /// Answers the proxy's authentication challenge, Chrome doesn't accept
+    /// credentials in the server url
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl Proxy {
+    pub fn new(server: impl Into<String>) -> Self {
+        Self {
+            server: server.into(),
+            username: None,
+            password: None,
+        }
+    }
+
+    /// Set the credentials for the proxy's authentication challenge
+    pub fn credentials(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+}
+
+impl From<&str> for Proxy {
+    fn from(server: &str) -> Self {
+        Self::new(server)
+    }
+}
+
+impl From<String> for Proxy {
+    fn from(server: String) -> Self {
+        Self::new(server)
+    }
+}
+
+/// A profile, its browser storage, proxy and metadata as one long-lived unit.
+///
+/// See the [module docs](self) for the directory layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Identity {
+    dir: PathBuf,
+    name: String,
+    profile: ChaserProfile,
+    user_data_dir: Option<PathBuf>,
+    proxy: Option<Proxy>,
+    metadata: BTreeMap<String, String>,
+}
+
+/// The on-disk representation of an [`Identity`]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IdentityFile {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user_data_dir: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    proxy: Option<Proxy>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+    profile: ChaserProfile,
+}
+
+impl Identity {
+    /// Create a builder for an identity stored in `dir`
+    pub fn builder(dir: impl AsRef<Path>, profile: ChaserProfile) -> IdentityBuilder {
+        let dir = dir.as_ref().to_path_buf();
+        let name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        IdentityBuilder {
+            dir,
+            name,
+            profile,
+            user_data_dir: None,
+            proxy: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Load the identity stored in `dir`
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, IdentityError> {
+        let dir = dir.as_ref();
+        let path = dir.join(IDENTITY_FILE);
+        let content = fs::read_to_string(&path).map_err(|source| IdentityError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let file: IdentityFile =
+            toml::from_str(&content).map_err(|source| IdentityError::Toml { path, source })?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            name: file.name,
+            profile: file.profile,
+            user_data_dir: file.user_data_dir,
+            proxy: file.proxy,
+            metadata: file.metadata,
+        })
+    }
+
+    /// Save the identity to its directory, creating it if needed. The user
+    /// data dir is left as it is.
+    pub fn save(&self) -> Result<(), IdentityError> {
+        let path = self.dir.join(IDENTITY_FILE);
+        let file = IdentityFile {
+            name: self.name.clone(),
+            user_data_dir: self.user_data_dir.clone(),
+            proxy: self.proxy.clone(),
+            metadata: self.metadata.clone(),
+            profile: self.profile.clone(),
+        };
+        let content = toml::to_string(&file).map_err(|source| IdentityError::Serialize {
+            path: path.clone(),
+            source,
+        })?;
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&path, content))
+            .map_err(|source| IdentityError::Io { path, source })
+    }
+
+    /// Launch a browser as this identity and return it with a page that has
+    /// the profile applied.
+    ///
+    /// Keep the [`Browser`] alive as long as the page is used, dropping it
+    /// closes the browser. Only one browser can use a user data dir at a time.
+    /// The proxy credentials are answered for every page the browser opens,
+    /// but not for workers, see [`Browser::authenticate`].
+    pub async fn launch(&self) -> Result<(Browser, ChaserPage)> {
+        self.launch_with(BrowserConfig::builder()).await
+    }
+
+    /// Like [`Self::launch`], starting from `config` for everything the
+    /// identity doesn't set, e.g. the headless mode or the executable
+    pub async fn launch_with(&self, config: BrowserConfigBuilder) -> Result<(Browser, ChaserPage)> {
+        let mut config = config
+            .profile(&self.profile)
+            .user_data_dir(self.user_data_dir());
+        if let Some(proxy) = &self.proxy {
+            config = config.arg(("proxy-server", proxy.server.as_str()));
+        }
+        let config = config.build().map_err(|e| anyhow!("{}", e))?;
+
+        let (browser, mut handler) = Browser::launch(config).await?;
+        tokio::spawn(async move { while handler.next().await.is_some() {} });
+
+        if let Some(Proxy {
+            username: Some(username),
+            password: Some(password),
+            ..
+        }) = &self.proxy
+        {
+            browser
+                .authenticate(Credentials {
+                    username: username.clone(),
+                    password: password.clone(),
+                })
+                .await?;
+        }
+
+        // the browser applies the profile to every page it opens
+        let page = browser.new_page("about:blank").await?;
+        let chaser = ChaserPage::new(page).with_keyboard_layout(self.profile.keyboard_layout());
+        Ok((browser, chaser))
+    }
+
+    /// The directory the identity is stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn profile(&self) -> &ChaserProfile {
+        &self.profile
+    }
+
+    /// Chrome's user data dir, `user-data` inside the identity's directory
+    /// unless set explicitly. Relative paths are resolved against the
+    /// identity's directory.
+    pub fn user_data_dir(&self) -> PathBuf {
+        match &self.user_data_dir {
+            Some(dir) => self.dir.join(dir),
+            None => self.dir.join(USER_DATA_DIR),
+        }
+    }
+
+    pub fn proxy(&self) -> Option<&Proxy> {
+        self.proxy.as_ref()
+    }
+
+    /// Returns the metadata value of `key`
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Iterate over all metadata in sorted order
+    pub fn metadata_iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.metadata.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Set a metadata value, e.g. when the identity was last used, returning
+    /// the one it replaced. Call [`Self::save`] to persist it.
+    pub fn set_metadata(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        self.metadata.insert(key.into(), value.into())
+    }
+}
+
+/// Builder for [`Identity`]
+#[derive(Debug, Clone)]
+pub struct IdentityBuilder {
+    dir: PathBuf,
+    name: String,
+    profile: ChaserProfile,
+    user_data_dir: Option<PathBuf>,
+    proxy: Option<Proxy>,
+    metadata: BTreeMap<String, String>,
+}
+
+impl IdentityBuilder {
+    /// Set the name (default: the name of the identity's directory)
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set Chrome's user data dir (default: `user-data` inside the identity's
+    /// directory). Relative paths are resolved against the identity's
+    /// directory.
+    pub fn user_data_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.user_data_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Connect through `proxy`. Pair it with a
+    /// [`WebRtcPolicy`](crate::profiles::WebRtcPolicy) on the profile so
+    /// WebRTC doesn't leak the host's address.
+    pub fn proxy(mut self, proxy: impl Into<Proxy>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Add a metadata value
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Build the final identity
+    pub fn build(self) -> Identity {
+        Identity {
+            dir: self.dir,
+            name: self.name,
+            profile: self.profile,
+            user_data_dir: self.user_data_dir,
+            proxy: self.proxy,
+            metadata: self.metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identities_round_trip_through_their_directory() {
+        let dir = std::env::temp_dir().join(format!("chaser-identity-{}", std::process::id()));
+        let identity = Identity::builder(&dir, ChaserProfile::macos_arm().locale("de-DE").build())
+            .proxy(Proxy::new("socks5://203.0.113.7:1080").credentials("user", "secret"))
+            .metadata("team", "growth")
+            .build();
+        assert_eq!(identity.user_data_dir(), dir.join("user-data"));
+        assert_eq!(identity.name(), dir.file_name().unwrap().to_str().unwrap());
+
+        identity.save().unwrap();
+        let loaded = Identity::load(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded, identity);
+        assert_eq!(loaded.metadata("team"), Some("growth"));
+        assert!(matches!(
+            Identity::load(&dir),
+            Err(IdentityError::Io { .. })
+        ));
+    }
+}
@@ -105,6 +105,24 @@ impl ElementQuad {
             y: (self.top_left.y + self.top_right.y + self.bottom_right.y + self.bottom_left.y) / 4.,
         }
     }
+    /// Returns the point at the relative position `u` along the top edge and
+    /// `v` along the left edge, `(0.5, 0.5)` is the center. Follows the
+    /// corners, so it stays inside rotated or skewed quads.
+    pub fn point_at(&self, u: f64, v: f64) -> Point {
+        let top = Point {
+            x: self.top_left.x + (self.top_right.x - self.top_left.x) * u,
+            y: self.top_left.y + (self.top_right.y - self.top_left.y) * u,
+        };
+        let bottom = Point {
+            x: self.bottom_left.x + (self.bottom_right.x - self.bottom_left.x) * u,
+            y: self.bottom_left.y + (self.bottom_right.y - self.bottom_left.y) * u,
+        };
+        Point {
+            x: top.x + (bottom.x - top.x) * v,
+            y: top.y + (bottom.y - top.y) * v,
+        }
+    }
+
     /// Compute sum of all directed areas of adjacent triangles
     /// https://en.wikipedia.org/wiki/Polygon#Simple_polygons
     pub fn quad_area(&self) -> f64 {
@@ -81,19 +81,24 @@ pub mod browser;
 pub mod cmd;
 pub mod conn;
 pub mod detection;
+pub mod diagnostics;
 pub mod element;
 pub mod error;
+pub mod evasions;
 #[cfg(feature = "fetcher")]
 pub mod fetcher {
     pub use chromiumoxide_fetcher::*;
 }
 pub mod async_process;
 pub mod handler;
+pub mod identity;
 pub mod js;
 pub mod keys;
 pub mod layout;
 pub mod listeners;
+pub mod motion;
 pub mod page;
+pub mod typing;
 pub(crate) mod utils;
 
 pub type ArcHttpRequest = Option<Arc<HttpRequest>>;
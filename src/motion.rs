@@ -0,0 +1,348 @@
+//! Mouse motion models for [`ChaserPage`](crate::ChaserPage).
+//!
+//! A [`MotionModel`] turns a movement into timed mouse events. Select one with
+//! [`ChaserPage::with_motion_model`](crate::ChaserPage::with_motion_model):
+//!
+//! - [`Bezier`]: a randomized cubic Bezier curve at a fixed 25 steps, the
+//!   default
+//! - [`WindMouse`]: a physics simulation where gravity pulls the pointer to
+//!   the target while wind pushes it around
+//! - [`MinimumJerk`]: straight-ish strokes with the bell-shaped velocity
+//!   profile of human arm movements, timed by Fitts's law
+//!
+//! ```rust,no_run
+//! use chaser_oxide::motion::MinimumJerk;
+//! use chaser_oxide::{ChaserPage, Page};
+//!
+//! # fn run(page: Page) {
+//! let chaser = ChaserPage::new(page).with_motion_model(MinimumJerk::default());
+//! # }
+//! ```
+
+use std::f64::consts::PI;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::chaser::{BezierPath, Point};
+
+/// Movements shorter than this are never overshot
+const OVERSHOOT_MIN_DISTANCE: f64 = 150.0;
+
+/// One mouse event of a movement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionStep {
+    /// Where the pointer moves to
+    pub point: Point,
+    /// How long to wait before the next step
+    pub delay: Duration,
+}
+
+/// Plans the path and timing of a mouse movement
+pub trait MotionModel: Debug + Send + Sync {
+    /// The steps from `start` to `end`, a target `target_width` pixels wide.
+    /// The last step must land exactly on `end`.
+    fn plan(&self, start: Point, end: Point, target_width: f64) -> Vec<MotionStep>;
+}
+
+/// The randomized Bezier curve of [`BezierPath`] in 25 steps, 5-15ms apart
+/// regardless of the distance
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bezier;
+
+impl MotionModel for Bezier {
+    fn plan(&self, start: Point, end: Point, _target_width: f64) -> Vec<MotionStep> {
+        let mut rng = rand::thread_rng();
+        BezierPath::generate(start, end, 25)
+            .into_iter()
+            .map(|point| MotionStep {
+                point,
+                delay: Duration::from_millis(rng.gen_range(5..15)),
+            })
+            .collect()
+    }
+}
+
+/// Benjamin Land's WindMouse.
+///
+/// The pointer speeds up while gravity pulls it towards the target and random
+/// wind bends its path, then slows down and wanders less inside
+/// `target_area`. Longer movements take proportionally more steps.
+#[derive(Debug, Clone, Copy)]
+pub struct WindMouse {
+    /// The pull towards the target
+    pub gravity: f64,
+    /// The strength of the random wind
+    pub wind: f64,
+    /// The maximum distance of one step in pixels
+    pub max_step: f64,
+    /// The distance from the target at which the pointer starts to slow down
+    pub target_area: f64,
+    /// The time between two steps
+    pub step_interval: Duration,
+    /// The chance of overshooting the target and correcting back to it
+    pub overshoot_chance: f64,
+}
+
+impl Default for WindMouse {
+    fn default() -> Self {
+        Self {
+            gravity: 9.0,
+            wind: 3.0,
+            max_step: 15.0,
+            target_area: 12.0,
+            step_interval: Duration::from_millis(8),
+            overshoot_chance: 0.2,
+        }
+    }
+}
+
+impl WindMouse {
+    fn movement(&self, start: Point, end: Point, rng: &mut impl Rng) -> Vec<MotionStep> {
+        let (sqrt3, sqrt5) = (3f64.sqrt(), 5f64.sqrt());
+        let mut steps = Vec::new();
+        let (mut x, mut y) = (start.x, start.y);
+        let (mut vx, mut vy, mut wx, mut wy) = (0.0, 0.0, 0.0, 0.0);
+        let mut max_step = self.max_step;
+
+        // the cap only guards against degenerate parameters
+        for _ in 0..10_000 {
+            let dist = (end.x - x).hypot(end.y - y);
+            if dist < 1.0 {
+                break;
+            }
+            let wind = self.wind.min(dist);
+            if dist >= self.target_area {
+                wx = wx / sqrt3 + rng.gen_range(-1.0..=1.0) * wind / sqrt5;
+                wy = wy / sqrt3 + rng.gen_range(-1.0..=1.0) * wind / sqrt5;
+            } else {
+                wx /= sqrt3;
+                wy /= sqrt3;
+                max_step = if max_step < 3.0 {
+                    rng.gen_range(3.0..6.0)
+                } else {
+                    max_step / sqrt5
+                };
+            }
+            vx += wx + self.gravity * (end.x - x) / dist;
+            vy += wy + self.gravity * (end.y - y) / dist;
+            let speed = vx.hypot(vy);
+            if speed > max_step {
+                let clipped = max_step / 2.0 * (1.0 + rng.gen::<f64>());
+                vx = vx / speed * clipped;
+                vy = vy / speed * clipped;
+            }
+            x += vx;
+            y += vy;
+            steps.push(MotionStep {
+                point: Point { x, y },
+                delay: jitter(self.step_interval, rng),
+            });
+        }
+
+        steps.push(MotionStep {
+            point: end,
+            delay: jitter(self.step_interval, rng),
+        });
+        steps
+    }
+}
+
+impl MotionModel for WindMouse {
+    fn plan(&self, start: Point, end: Point, _target_width: f64) -> Vec<MotionStep> {
+        let mut rng = rand::thread_rng();
+        with_overshoot(
+            start,
+            end,
+            self.overshoot_chance,
+            &mut rng,
+            |from, to, rng| self.movement(from, to, rng),
+        )
+    }
+}
+
+/// Minimum-jerk strokes timed by Fitts's law.
+///
+/// A stroke takes `intercept + slope * log2(distance / target_width + 1)`, so
+/// long movements to small targets are slow and short ones to large targets
+/// quick. Along the stroke the pointer follows the minimum-jerk profile
+/// `10t³ - 15t⁴ + 6t⁵`, accelerating smoothly to its peak speed halfway and
+/// decelerating into the target, on a slightly bowed line.
+#[derive(Debug, Clone, Copy)]
+pub struct MinimumJerk {
+    /// The time every stroke takes regardless of its difficulty
+    pub intercept: Duration,
+    /// The additional time per bit of difficulty
+    pub slope: Duration,
+    /// The time between two mouse events
+    pub event_interval: Duration,
+    /// The maximum sideways bow of the path relative to its length
+    pub curvature: f64,
+    /// The chance of overshooting the target and correcting back to it
+    pub overshoot_chance: f64,
+}
+
+impl Default for MinimumJerk {
+    fn default() -> Self {
+        Self {
+            intercept: Duration::from_millis(50),
+            slope: Duration::from_millis(120),
+            event_interval: Duration::from_millis(10),
+            curvature: 0.08,
+            overshoot_chance: 0.25,
+        }
+    }
+}
+
+impl MinimumJerk {
+    /// The Fitts's law duration of a stroke, without randomization
+    pub fn duration(&self, distance: f64, target_width: f64) -> Duration {
+        let difficulty = (distance / target_width.max(1.0) + 1.0).log2();
+        self.intercept + self.slope.mul_f64(difficulty)
+    }
+
+    fn stroke(
+        &self,
+        start: Point,
+        end: Point,
+        target_width: f64,
+        rng: &mut impl Rng,
+    ) -> Vec<MotionStep> {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let distance = dx.hypot(dy);
+        let duration = self
+            .duration(distance, target_width)
+            .mul_f64(rng.gen_range(0.85..1.15));
+        let count = (duration.as_secs_f64() / self.event_interval.as_secs_f64().max(0.001))
+            .ceil()
+            .max(2.0) as usize;
+        let curvature = self.curvature.abs();
+        let bow = distance * rng.gen_range(-curvature..=curvature);
+        // the unit normal of the movement
+        let (nx, ny) = if distance > 0.0 {
+            (-dy / distance, dx / distance)
+        } else {
+            (0.0, 0.0)
+        };
+
+        (1..=count)
+            .map(|i| {
+                let t = i as f64 / count as f64;
+                let progress = t.powi(3) * (10.0 - 15.0 * t + 6.0 * t.powi(2));
+                let offset = bow * (PI * progress).sin();
+                let point = if i == count {
+                    end
+                } else {
+                    Point {
+                        x: start.x + dx * progress + nx * offset,
+                        y: start.y + dy * progress + ny * offset,
+                    }
+                };
+                MotionStep {
+                    point,
+                    delay: jitter(self.event_interval, rng),
+                }
+            })
+            .collect()
+    }
+}
+
+impl MotionModel for MinimumJerk {
+    fn plan(&self, start: Point, end: Point, target_width: f64) -> Vec<MotionStep> {
+        let mut rng = rand::thread_rng();
+        with_overshoot(
+            start,
+            end,
+            self.overshoot_chance,
+            &mut rng,
+            |from, to, rng| self.stroke(from, to, target_width, rng),
+        )
+    }
+}
+
+/// Plans `start` to `end` with `movement`, sometimes as a primary movement
+/// past the target followed by a short correction back to it
+fn with_overshoot<R: Rng>(
+    start: Point,
+    end: Point,
+    chance: f64,
+    rng: &mut R,
+    mut movement: impl FnMut(Point, Point, &mut R) -> Vec<MotionStep>,
+) -> Vec<MotionStep> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let distance = dx.hypot(dy);
+    if distance < OVERSHOOT_MIN_DISTANCE || !rng.gen_bool(chance.clamp(0.0, 1.0)) {
+        return movement(start, end, rng);
+    }
+
+    let along = rng.gen_range(0.03..0.08);
+    let aside = rng.gen_range(-0.02..0.02);
+    let overshoot = Point {
+        x: end.x + dx * along - dy * aside,
+        y: end.y + dy * along + dx * aside,
+    };
+    let mut steps = movement(start, overshoot, rng);
+    // noticing the miss takes a moment
+    if let Some(last) = steps.last_mut() {
+        last.delay += Duration::from_millis(rng.gen_range(60..150));
+    }
+    steps.extend(movement(overshoot, end, rng));
+    steps
+}
+
+/// `interval` randomized by ±20%
+fn jitter(interval: Duration, rng: &mut impl Rng) -> Duration {
+    interval.mul_f64(rng.gen_range(0.8..1.2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total(steps: &[MotionStep]) -> Duration {
+        steps.iter().map(|s| s.delay).sum()
+    }
+
+    #[test]
+    fn paths_end_on_the_target() {
+        let start = Point { x: 10.0, y: 20.0 };
+        let end = Point { x: 900.0, y: 500.0 };
+        let models: [&dyn MotionModel; 3] =
+            [&Bezier, &WindMouse::default(), &MinimumJerk::default()];
+        for model in models {
+            for _ in 0..20 {
+                let steps = model.plan(start, end, 20.0);
+                assert_eq!(steps.last().unwrap().point, end, "{model:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn minimum_jerk_follows_fitts_law() {
+        let model = MinimumJerk {
+            overshoot_chance: 0.0,
+            ..Default::default()
+        };
+        let start = Point { x: 0.0, y: 0.0 };
+        let near = Point { x: 10.0, y: 0.0 };
+        let far = Point { x: 1500.0, y: 0.0 };
+        assert!(model.duration(1500.0, 20.0) > model.duration(1500.0, 200.0));
+        assert!(total(&model.plan(start, far, 20.0)) > total(&model.plan(start, near, 20.0)) * 3);
+
+        // slow at both ends, fastest in the middle
+        let steps = model.plan(start, far, 20.0);
+        let speeds = steps
+            .windows(2)
+            .map(|w| w[1].point.x - w[0].point.x)
+            .collect::<Vec<_>>();
+        let middle = speeds[speeds.len() / 2];
+        assert!(speeds[0] < middle / 4.0 && speeds[speeds.len() - 1] < middle / 4.0);
+
+        let bowed = MinimumJerk {
+            curvature: -0.1,
+            ..model
+        };
+        assert_eq!(bowed.plan(start, far, 20.0).last().unwrap().point, far);
+    }
+}
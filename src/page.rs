@@ -29,14 +29,18 @@ use chromiumoxide_types::*;
 use crate::auth::Credentials;
 use crate::element::Element;
 use crate::error::{CdpError, Result};
+use crate::evasions::EvasionSet;
 use crate::handler::commandfuture::CommandFuture;
 use crate::handler::domworld::DOMWorldKind;
+use crate::handler::emulation::ProfileCommands;
 use crate::handler::httpfuture::HttpFuture;
 use crate::handler::target::{GetName, GetParent, GetUrl, TargetMessage};
 use crate::handler::PageInner;
 use crate::js::{Evaluation, EvaluationResult};
 use crate::layout::Point;
 use crate::listeners::{EventListenerRequest, EventStream};
+use crate::profiles::ChaserProfile;
+use crate::stealth::StealthProfile;
 use crate::{utils, ArcHttpRequest};
 
 #[derive(Debug, Clone)]
@@ -45,283 +49,94 @@ pub struct Page {
 }
 
 impl Page {
-    /// Removes the `navigator.webdriver` property
-    /// changes permissions, pluggins rendering contexts and the `window.chrome`
-    /// property to make it harder to detect the scraper as a bot
-    async fn _enable_stealth_mode(&self) -> Result<()> {
-        self.hide_hardware_harmony().await?; // MUST be first - establishes consistent identity
-        self.hide_webdriver().await?;
-        self.hide_permissions().await?;
-        self.hide_plugins().await?;
-        self.hide_webgl_vendor().await?;
-        self.hide_chrome().await?;
-        self.hide_codecs().await?;
-
-        Ok(())
-    }
-
     /// Changes your user_agent, removes the `navigator.webdriver` property
     /// changes permissions, pluggins rendering contexts and the `window.chrome`
-    /// property to make it harder to detect the scraper as a bot
+    /// property to make it harder to detect the scraper as a bot.
+    ///
+    /// Uses the user agent, client hints and scripts of the default
+    /// [`ChaserProfile`], but leaves the timezone, locale, `Accept-Language`
+    /// and screen alone. See [`Page::enable_stealth_mode_with_profile`] to
+    /// apply a whole identity.
     pub async fn enable_stealth_mode(&self) -> Result<()> {
-        self._enable_stealth_mode().await?;
-        self.hide_client_hints().await?;
-        self.set_user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/129.0.0.0 Safari/537.36").await?;
-
-        Ok(())
-    }
-
-    /// Patches navigator.userAgentData to hide HeadlessChrome
-    async fn hide_client_hints(&self) -> Result<(), CdpError> {
-        self.execute(AddScriptToEvaluateOnNewDocumentParams {
-            source: r#"
-                Object.defineProperty(Navigator.prototype, 'userAgentData', {
-                    get: () => ({
-                        brands: [
-                            { brand: "Google Chrome", version: "129" },
-                            { brand: "Chromium", version: "129" },
-                            { brand: "Not=A?Brand", version: "24" }
-                        ],
-                        mobile: false,
-                        platform: "Windows"
-                    }),
-                    configurable: true
-                });
-            "#
-            .to_string(),
-            world_name: None,
-            include_command_line_api: None,
-            run_immediately: None,
-        })
-        .await?;
-        Ok(())
+        self.enable_stealth_mode_without_emulation(&ChaserProfile::default())
+            .await
     }
 
     /// Changes your user_agent with a custom agent, removes the `navigator.webdriver` property
     /// changes permissions, pluggins rendering contexts and the `window.chrome`
     /// property to make it harder to detect the scraper as a bot
+    ///
+    /// Like [`Page::enable_stealth_mode`] with [`ChaserProfile::from_user_agent`],
+    /// so the client hints and `navigator.userAgentData` follow the OS and
+    /// Chrome version of `ua`. An empty `ua` uses the default profile.
     pub async fn enable_stealth_mode_with_agent(&self, ua: &str) -> Result<()> {
-        self._enable_stealth_mode().await?;
-        if !ua.is_empty() {
-            self.set_user_agent(ua).await?;
+        if ua.is_empty() {
+            return self.enable_stealth_mode().await;
         }
-        Ok(())
-    }
-
-    /// **Hardware Harmony**: Establishes a consistent Windows/NVIDIA identity.
-    ///
-    /// Fixes the "Platform Chimera" issue where User-Agent says Windows but
-    /// navigator.platform says MacIntel. This is a critical anti-detection fix.
-    async fn hide_hardware_harmony(&self) -> Result<(), CdpError> {
-        self.execute(AddScriptToEvaluateOnNewDocumentParams {
-            source: r#"
-                // 1. Fix Platform - MUST match Windows User-Agent (on prototype)
-                Object.defineProperty(Navigator.prototype, 'platform', { 
-                    get: () => 'Win32',
-                    configurable: true
-                });
-
-                // 2. Align Hardware specs for a "typical gamer PC" profile (on prototype)
-                Object.defineProperty(Navigator.prototype, 'hardwareConcurrency', { 
-                    get: () => 8,
-                    configurable: true 
-                });
-                Object.defineProperty(Navigator.prototype, 'deviceMemory', { 
-                    get: () => 8,
-                    configurable: true
-                });
-            "#
-            .to_string(),
-            world_name: None,
-            include_command_line_api: None,
-            run_immediately: None,
-        })
-        .await?;
-        Ok(())
+        self.enable_stealth_mode_without_emulation(&ChaserProfile::from_user_agent(ua))
+            .await
     }
 
-    /// Sets `window.chrome` on frame creation
-    async fn hide_chrome(&self) -> Result<(), CdpError> {
-        self.execute(AddScriptToEvaluateOnNewDocumentParams {
-            source: "window.chrome = { runtime: {} };".to_string(),
-            world_name: None,
-            include_command_line_api: None,
-            run_immediately: None,
-        })
-        .await?;
-        Ok(())
+    async fn enable_stealth_mode_without_emulation(&self, profile: &ChaserProfile) -> Result<()> {
+        let commands = ProfileCommands::new(profile, profile.bootstrap_script())?;
+        self.apply_profile_commands(commands.without_emulation())
+            .await
     }
 
-    /// Obfuscates WebGL vendor/renderer to match Windows NVIDIA profile
-    async fn hide_webgl_vendor(&self) -> Result<(), CdpError> {
-        self
-            .execute(AddScriptToEvaluateOnNewDocumentParams {
-                source: r#"
-                    // Spoof WebGL for both WebGL1 and WebGL2 contexts
-                    const spoofWebGL = (proto) => {
-                        const getParameter = proto.getParameter;
-                        proto.getParameter = function(parameter) {
-                            // UNMASKED_VENDOR_WEBGL
-                            if (parameter === 37445) {
-                                return 'Google Inc. (NVIDIA)';
-                            }
-                            // UNMASKED_RENDERER_WEBGL - RTX 3080 is a high-trust profile
-                            if (parameter === 37446) {
-                                return 'ANGLE (NVIDIA, NVIDIA GeForce RTX 3080 Direct3D11 vs_5_0 ps_5_0)';
-                            }
-                            return getParameter.apply(this, arguments);
-                        };
-                    };
-                    spoofWebGL(WebGLRenderingContext.prototype);
-                    if (typeof WebGL2RenderingContext !== 'undefined') {
-                        spoofWebGL(WebGL2RenderingContext.prototype);
-                    }
-                "#.to_string(),
-                world_name: None,
-                include_command_line_api: None,
-                run_immediately: None,
-            })
-            .await?;
-        Ok(())
-    }
-
-    /// Spoofs video codec support (H.264/AAC) - critical for Turnstile/DataDome
-    async fn hide_codecs(&self) -> Result<(), CdpError> {
-        self.execute(AddScriptToEvaluateOnNewDocumentParams {
-            source: r#"
-                const canPlayType = HTMLMediaElement.prototype.canPlayType;
-                HTMLMediaElement.prototype.canPlayType = function(type) {
-                    // H.264 video codec (critical - headless often fails this)
-                    if (type.includes('avc1')) return 'probably';
-                    // AAC audio codec
-                    if (type.includes('mp4a.40')) return 'probably';
-                    // MP4 container
-                    if (type === 'video/mp4') return 'probably';
-                    if (type === 'audio/mp4') return 'probably';
-                    if (type === 'video/x-m4v') return 'probably';
-                    return canPlayType.apply(this, arguments);
-                };
-            "#
-            .to_string(),
-            world_name: None,
-            include_command_line_api: None,
-            run_immediately: None,
-        })
-        .await?;
-        Ok(())
-    }
-
-    /// Obfuscates browser plugins on frame creation
-    async fn hide_plugins(&self) -> Result<(), CdpError> {
-        self.execute(AddScriptToEvaluateOnNewDocumentParams {
-            source: r#"
-                // Create a proper PluginArray-like object (NOT an Array!)
-                // Key insight: PluginArray is array-like but Array.isArray() returns false
-                
-                const makePlugin = (name, filename, description) => {
-                    const plugin = Object.create(Plugin.prototype);
-                    Object.defineProperties(plugin, {
-                        name: { value: name, enumerable: true },
-                        filename: { value: filename, enumerable: true },
-                        description: { value: description, enumerable: true },
-                        length: { value: 1, enumerable: true },
-                        0: { value: { type: 'application/pdf', suffixes: 'pdf', description }, enumerable: true }
-                    });
-                    return plugin;
-                };
-                
-                // Create the fake PluginArray using the real PluginArray prototype
-                const fakePlugins = Object.create(PluginArray.prototype);
-                
-                const plugins = [
-                    makePlugin('PDF Viewer', 'internal-pdf-viewer', 'Portable Document Format'),
-                    makePlugin('Chrome PDF Viewer', 'internal-pdf-viewer', 'Portable Document Format'),
-                    makePlugin('Chromium PDF Viewer', 'internal-pdf-viewer', 'Portable Document Format'),
-                    makePlugin('Microsoft Edge PDF Viewer', 'internal-pdf-viewer', 'Portable Document Format'),
-                    makePlugin('WebKit built-in PDF', 'internal-pdf-viewer', 'Portable Document Format')
-                ];
-                
-                // Add indexed access and length
-                plugins.forEach((p, i) => {
-                    Object.defineProperty(fakePlugins, i, { value: p, enumerable: true });
-                });
-                Object.defineProperty(fakePlugins, 'length', { value: plugins.length, enumerable: true });
-                
-                // Add methods
-                Object.defineProperty(fakePlugins, 'item', { 
-                    value: function(index) { return this[index] || null; },
-                    enumerable: false
-                });
-                Object.defineProperty(fakePlugins, 'namedItem', { 
-                    value: function(name) { 
-                        for (let i = 0; i < this.length; i++) {
-                            if (this[i].name === name) return this[i];
-                        }
-                        return null;
-                    },
-                    enumerable: false
-                });
-                Object.defineProperty(fakePlugins, 'refresh', { 
-                    value: function() {},
-                    enumerable: false
-                });
-                
-                // Make it iterable
-                Object.defineProperty(fakePlugins, Symbol.iterator, {
-                    value: function* () {
-                        for (let i = 0; i < this.length; i++) yield this[i];
-                    },
-                    enumerable: false
-                });
-                
-                Object.defineProperty(Navigator.prototype, 'plugins', {
-                    get: () => fakePlugins,
-                    configurable: true
-                });
-            "#.to_string(),
-            world_name: None,
-            include_command_line_api: None,
-            run_immediately: None,
-        })
-        .await?;
-        Ok(())
+    /// Applies `profile` with [`Page::apply_profile`]
+    pub async fn enable_stealth_mode_with_profile(
+        &self,
+        profile: &impl StealthProfile,
+    ) -> Result<()> {
+        self.apply_profile(profile).await
     }
 
-    /// Obfuscates browser permissions on frame creation
-    async fn hide_permissions(&self) -> Result<(), CdpError> {
-        self.execute(AddScriptToEvaluateOnNewDocumentParams {
-            source: "
-                    const originalQuery = window.navigator.permissions.query;
-                    window.navigator.permissions.__proto__.query = parameters => {
-                        return parameters.name === 'notifications'
-                            ? Promise.resolve({ state: Notification.permission })
-                            : originalQuery(parameters);
-                    }
-                "
-            .to_string(),
-            world_name: None,
-            include_command_line_api: None,
-            run_immediately: None,
-        })
-        .await?;
-        Ok(())
+    /// Applies a browser identity to this page.
+    ///
+    /// Sets the User-Agent, client hints and `Accept-Language` headers,
+    /// emulates the profile's timezone, locale, screen and mobile viewport,
+    /// and injects the profile's bootstrap script into every new document.
+    /// Out-of-process iframes and dedicated, shared and service workers the
+    /// page starts get the same patches before they run. Call this before
+    /// navigating.
+    pub async fn apply_profile(&self, profile: &impl StealthProfile) -> Result<()> {
+        self.apply_profile_commands(ProfileCommands::new(profile, profile.bootstrap_script())?)
+            .await
+    }
+
+    /// Like [`Page::apply_profile`], but injects the script rendered by
+    /// `evasions` instead of the profile's default one
+    pub async fn apply_profile_with_evasions(
+        &self,
+        profile: &impl StealthProfile,
+        evasions: &EvasionSet,
+    ) -> Result<()> {
+        self.apply_profile_commands(ProfileCommands::new(profile, evasions.script(profile))?)
+            .await
     }
 
-    /// Sets `navigator.webdriver` to false on frame creation
-    async fn hide_webdriver(&self) -> Result<(), CdpError> {
-        self.execute(AddScriptToEvaluateOnNewDocumentParams {
-            source: "
-                    Object.defineProperty(Object.getPrototypeOf(navigator), 'webdriver', {
-                        get: () => false,
-                        configurable: true
-                    });
-                "
-            .to_string(),
-            world_name: None,
-            include_command_line_api: None,
-            run_immediately: None,
-        })
-        .await?;
+    /// Runs `commands` on this page and hands them to the target, which
+    /// applies them to the page's out-of-process iframes and workers
+    async fn apply_profile_commands(&self, commands: ProfileCommands) -> Result<()> {
+        self.set_user_agent(commands.user_agent.clone()).await?;
+        if let Some(timezone) = commands.timezone.clone() {
+            self.emulate_timezone(timezone).await?;
+        }
+        if let Some(locale) = commands.locale.clone() {
+            self.emulate_locale(locale).await?;
+        }
+        if let Some(device_metrics) = commands.device_metrics.clone() {
+            self.execute(device_metrics).await?;
+        }
+        if let Some(touch) = commands.touch.clone() {
+            self.execute(touch).await?;
+        }
+        self.execute(commands.add_script()).await?;
+        self.inner
+            .sender()
+            .clone()
+            .send(TargetMessage::Profile(Arc::new(commands)))
+            .await?;
         Ok(())
     }
 
@@ -0,0 +1,391 @@
+//! Per-OS defaults for the device APIs next to `navigator`: the network
+//! connection, the battery, media devices and speech synthesis voices.
+//!
+//! Headless Chrome answers all of them with values no desktop or phone
+//! reports, no voices, no media devices and a battery that is always
+//! charging, so each profile ships what a stock installation of its OS
+//! exposes.
+
+use serde::{Deserialize, Serialize};
+
+use super::{ChaserProfile, Os};
+
+/// The values of `navigator.connection`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkConnection {
+    /// `effectiveType`, e.g. `"4g"`
+    pub effective_type: String,
+    /// `rtt` in milliseconds, Chrome rounds it to multiples of 25
+    pub rtt: u32,
+    /// `downlink` in Mbit/s, Chrome rounds it to multiples of 0.025
+    pub downlink: f64,
+    /// `saveData`
+    pub save_data: bool,
+}
+
+impl Default for NetworkConnection {
+    /// A wired or WiFi broadband connection
+    fn default() -> Self {
+        Self {
+            effective_type: "4g".to_string(),
+            rtt: 50,
+            downlink: 10.0,
+            save_data: false,
+        }
+    }
+}
+
+/// The state `navigator.getBattery()` resolves with
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Battery {
+    /// `level` between 0 and 1
+    pub level: f64,
+    /// `charging`
+    pub charging: bool,
+    /// `chargingTime` in seconds, `None` reports `Infinity`
+    pub charging_time: Option<u32>,
+    /// `dischargingTime` in seconds, `None` reports `Infinity`
+    pub discharging_time: Option<u32>,
+}
+
+impl Battery {
+    /// A machine without a battery, which Chrome reports as fully charged
+    /// and plugged in
+    pub fn plugged_in() -> Self {
+        Self {
+            level: 1.0,
+            charging: true,
+            charging_time: Some(0),
+            discharging_time: None,
+        }
+    }
+
+    /// A battery at `level` charging, full in `seconds`
+    pub fn charging(level: f64, seconds: u32) -> Self {
+        Self {
+            level,
+            charging: true,
+            charging_time: Some(seconds),
+            discharging_time: None,
+        }
+    }
+
+    /// A battery at `level` running on battery, empty in `seconds`
+    pub fn discharging(level: f64, seconds: u32) -> Self {
+        Self {
+            level,
+            charging: false,
+            charging_time: None,
+            discharging_time: Some(seconds),
+        }
+    }
+}
+
+impl Default for Battery {
+    fn default() -> Self {
+        Self::plugged_in()
+    }
+}
+
+/// The devices `navigator.mediaDevices.enumerateDevices()` lists.
+///
+/// Until the page is granted camera or microphone access Chrome lists at
+/// most one device of each kind, without id or label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaDevices {
+    pub audio_inputs: u32,
+    pub video_inputs: u32,
+    pub audio_outputs: u32,
+}
+
+impl Default for MediaDevices {
+    /// A microphone, a webcam and speakers
+    fn default() -> Self {
+        Self {
+            audio_inputs: 1,
+            video_inputs: 1,
+            audio_outputs: 1,
+        }
+    }
+}
+
+/// A voice of `speechSynthesis.getVoices()`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeechVoice {
+    /// `name`, also reported as `voiceURI`
+    pub name: String,
+    /// `lang`, a BCP 47 language tag
+    pub lang: String,
+    /// `localService`, `false` for voices synthesized by a server
+    pub local_service: bool,
+    /// `default`
+    #[serde(default)]
+    pub default: bool,
+}
+
+impl SpeechVoice {
+    /// A voice installed on the device
+    pub fn local(name: impl Into<String>, lang: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            lang: lang.into(),
+            local_service: true,
+            default: false,
+        }
+    }
+
+    /// A voice synthesized by a server, like Chrome's Google voices
+    pub fn remote(name: impl Into<String>, lang: impl Into<String>) -> Self {
+        Self {
+            local_service: false,
+            ..Self::local(name, lang)
+        }
+    }
+
+    /// Marks the voice as the default one
+    pub fn as_default(mut self) -> Self {
+        self.default = true;
+        self
+    }
+
+    /// The network voices every desktop Chrome ships
+    pub fn google_voices() -> Vec<SpeechVoice> {
+        GOOGLE_VOICES
+            .iter()
+            .map(|(name, lang)| SpeechVoice::remote(*name, *lang))
+            .collect()
+    }
+}
+
+impl Os {
+    /// Returns the `navigator.connection` of this OS, `None` where the API
+    /// doesn't exist
+    pub fn default_connection(&self) -> Option<NetworkConnection> {
+        match self {
+            Os::Windows | Os::MacOSIntel | Os::MacOSArm | Os::Linux => {
+                Some(NetworkConnection::default())
+            }
+            Os::Android => Some(NetworkConnection {
+                rtt: 100,
+                downlink: 5.65,
+                ..NetworkConnection::default()
+            }),
+            // WebKit has no Network Information API
+            Os::Ios => None,
+        }
+    }
+
+    /// Returns the battery of a typical device of this OS, `None` where the
+    /// Battery Status API doesn't exist. [`ChaserProfile::battery`] varies the
+    /// charge of discharging batteries per profile.
+    pub fn default_battery(&self) -> Option<Battery> {
+        match self {
+            Os::Windows | Os::Linux => Some(Battery::plugged_in()),
+            // most Macs running Chrome are laptops
+            Os::MacOSIntel | Os::MacOSArm => Some(Battery::discharging(0.87, 15_960)),
+            Os::Android => Some(Battery::discharging(0.64, 21_240)),
+            Os::Ios => None,
+        }
+    }
+
+    /// Returns the media devices of a typical device of this OS
+    pub fn default_media_devices(&self) -> MediaDevices {
+        match self {
+            Os::Windows | Os::MacOSIntel | Os::MacOSArm | Os::Android => MediaDevices::default(),
+            // desktop towers rarely have a webcam
+            Os::Linux => MediaDevices {
+                video_inputs: 0,
+                ..MediaDevices::default()
+            },
+            // WebKit doesn't list audio outputs
+            Os::Ios => MediaDevices {
+                audio_outputs: 0,
+                ..MediaDevices::default()
+            },
+        }
+    }
+
+    /// Returns the speech synthesis voices of a stock installation of this OS
+    pub fn default_speech_voices(&self) -> Vec<SpeechVoice> {
+        let local = |voices: &[(&str, &str)]| {
+            voices
+                .iter()
+                .enumerate()
+                .map(|(i, (name, lang))| {
+                    let voice = SpeechVoice::local(*name, *lang);
+                    if i == 0 {
+                        voice.as_default()
+                    } else {
+                        voice
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        match self {
+            Os::Windows => [local(WINDOWS_VOICES), SpeechVoice::google_voices()].concat(),
+            Os::MacOSIntel | Os::MacOSArm => {
+                [local(MACOS_VOICES), SpeechVoice::google_voices()].concat()
+            }
+            Os::Linux => SpeechVoice::google_voices(),
+            Os::Android => local(ANDROID_VOICES),
+            Os::Ios => local(MACOS_VOICES),
+        }
+    }
+}
+
+impl ChaserProfile {
+    /// Returns `navigator.connection`, the OS default unless overridden with
+    /// [`ChaserProfileBuilder::connection`]
+    ///
+    /// [`ChaserProfileBuilder::connection`]: super::ChaserProfileBuilder::connection
+    pub fn connection(&self) -> Option<NetworkConnection> {
+        self.connection
+            .clone()
+            .or_else(|| self.os.default_connection())
+    }
+
+    /// Returns the battery state, the OS default unless overridden with
+    /// [`ChaserProfileBuilder::battery`].
+    ///
+    /// A discharging default battery gets its level and remaining time from
+    /// the [noise seed](Self::noise_seed), so profiles of the same OS don't
+    /// all report the same charge.
+    ///
+    /// [`ChaserProfileBuilder::battery`]: super::ChaserProfileBuilder::battery
+    pub fn battery(&self) -> Option<Battery> {
+        if self.battery.is_some() {
+            return self.battery;
+        }
+        let battery = self.os.default_battery()?;
+        match (battery.charging, self.noise_seed()) {
+            (false, Some(seed)) => {
+                let level = f64::from(20 + seed % 80) / 100.0;
+                // a full charge lasts 5 to 9 hours
+                let full = 18_000 + (seed / 80) % 14_400;
+                let seconds = (level * f64::from(full) / 60.0).round() as u32 * 60;
+                Some(Battery::discharging(level, seconds))
+            }
+            _ => Some(battery),
+        }
+    }
+
+    /// Returns the media devices, the OS default unless overridden with
+    /// [`ChaserProfileBuilder::media_devices`]
+    ///
+    /// [`ChaserProfileBuilder::media_devices`]: super::ChaserProfileBuilder::media_devices
+    pub fn media_devices(&self) -> MediaDevices {
+        self.media_devices
+            .unwrap_or_else(|| self.os.default_media_devices())
+    }
+
+    /// Returns the speech synthesis voices, the OS default unless overridden
+    /// with [`ChaserProfileBuilder::speech_voices`]
+    ///
+    /// [`ChaserProfileBuilder::speech_voices`]: super::ChaserProfileBuilder::speech_voices
+    pub fn speech_voices(&self) -> Vec<SpeechVoice> {
+        self.speech_voices
+            .clone()
+            .unwrap_or_else(|| self.os.default_speech_voices())
+    }
+}
+
+const GOOGLE_VOICES: &[(&str, &str)] = &[
+    ("Google Deutsch", "de-DE"),
+    ("Google US English", "en-US"),
+    ("Google UK English Female", "en-GB"),
+    ("Google UK English Male", "en-GB"),
+    ("Google español", "es-ES"),
+    ("Google español de Estados Unidos", "es-US"),
+    ("Google français", "fr-FR"),
+    ("Google हिन्दी", "hi-IN"),
+    ("Google Bahasa Indonesia", "id-ID"),
+    ("Google italiano", "it-IT"),
+    ("Google 日本語", "ja-JP"),
+    ("Google 한국의", "ko-KR"),
+    ("Google Nederlands", "nl-NL"),
+    ("Google polski", "pl-PL"),
+    ("Google português do Brasil", "pt-BR"),
+    ("Google русский", "ru-RU"),
+    ("Google 普通话（中国大陆）", "zh-CN"),
+    ("Google 粤語（香港）", "zh-HK"),
+    ("Google 國語（臺灣）", "zh-TW"),
+];
+
+const WINDOWS_VOICES: &[(&str, &str)] = &[
+    ("Microsoft David - English (United States)", "en-US"),
+    ("Microsoft Mark - English (United States)", "en-US"),
+    ("Microsoft Zira - English (United States)", "en-US"),
+];
+
+const MACOS_VOICES: &[(&str, &str)] = &[
+    ("Samantha", "en-US"),
+    ("Daniel", "en-GB"),
+    ("Karen", "en-AU"),
+    ("Moira", "en-IE"),
+    ("Rishi", "en-IN"),
+    ("Tessa", "en-ZA"),
+    ("Anna", "de-DE"),
+    ("Thomas", "fr-FR"),
+    ("Alice", "it-IT"),
+    ("Mónica", "es-ES"),
+    ("Paulina", "es-MX"),
+    ("Luciana", "pt-BR"),
+    ("Milena", "ru-RU"),
+    ("Kyoko", "ja-JP"),
+    ("Yuna", "ko-KR"),
+    ("Tingting", "zh-CN"),
+];
+
+const ANDROID_VOICES: &[(&str, &str)] = &[
+    ("English United States", "en-US"),
+    ("English United Kingdom", "en-GB"),
+    ("English India", "en-IN"),
+    ("Deutsch Deutschland", "de-DE"),
+    ("español España", "es-ES"),
+    ("français France", "fr-FR"),
+    ("italiano Italia", "it-IT"),
+    ("português Brasil", "pt-BR"),
+    ("日本語 日本", "ja-JP"),
+    ("한국어 대한민국", "ko-KR"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_apis_follow_the_os() {
+        let windows = ChaserProfile::windows().build();
+        assert_eq!(windows.battery(), Some(Battery::plugged_in()));
+        assert_eq!(windows.media_devices().video_inputs, 1);
+        let voices = windows.speech_voices();
+        assert!(voices[0].name.starts_with("Microsoft David") && voices[0].default);
+        assert!(voices.iter().any(|v| v.name == "Google US English"));
+
+        // discharging batteries differ per profile
+        let mac = |seed| {
+            ChaserProfile::macos_arm()
+                .noise_seed(seed)
+                .build()
+                .battery()
+                .unwrap()
+        };
+        assert!(!mac(1).charging && mac(1).discharging_time.is_some());
+        assert_ne!(mac(1), mac(2));
+        assert_eq!(mac(1), mac(1));
+        assert!((0.2..1.0).contains(&mac(3).level));
+
+        let ios = ChaserProfile::ios().build();
+        assert!(ios.connection().is_none() && ios.battery().is_none());
+        assert!(!ios.speech_voices().iter().any(|v| !v.local_service));
+
+        let custom = ChaserProfile::macos_arm()
+            .battery(Battery::charging(0.5, 1800))
+            .speech_voices([SpeechVoice::local("Samantha", "en-US").as_default()])
+            .build();
+        assert!(custom.battery().unwrap().charging);
+        assert_eq!(custom.speech_voices().len(), 1);
+        let toml = custom.to_toml_string().unwrap();
+        assert_eq!(ChaserProfile::from_toml_str(&toml).unwrap(), custom);
+    }
+}
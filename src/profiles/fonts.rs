@@ -0,0 +1,353 @@
+//! Per-OS font availability.
+//!
+//! Font probes measure text rendered with a candidate family against a
+//! generic fallback, or ask `document.fonts.check` and `queryLocalFonts`
+//! directly. Left alone they report the host's fonts, so a Windows profile in
+//! a Linux container shows DejaVu and Liberation instead of Segoe UI. The
+//! profile script rewrites every family outside the profile's allowlist to a
+//! family that doesn't exist, which makes the browser fall back exactly as it
+//! does for a missing font.
+//!
+//! Fonts on the allowlist still have to be installed on the host to render,
+//! otherwise text probes see them as missing too.
+
+use super::{ChaserProfile, Os};
+
+impl Os {
+    /// Returns the fonts a stock installation of this OS ships with
+    pub fn default_fonts(&self) -> &'static [&'static str] {
+        match self {
+            Os::Windows => WINDOWS_FONTS,
+            Os::MacOSIntel | Os::MacOSArm => MACOS_FONTS,
+            Os::Linux => LINUX_FONTS,
+            Os::Android => ANDROID_FONTS,
+            Os::Ios => IOS_FONTS,
+        }
+    }
+}
+
+impl ChaserProfile {
+    /// Returns the font families this profile exposes, the OS defaults unless
+    /// overridden with [`ChaserProfileBuilder::fonts`].
+    ///
+    /// [`ChaserProfileBuilder::fonts`]: super::ChaserProfileBuilder::fonts
+    pub fn fonts(&self) -> Vec<&str> {
+        match &self.fonts {
+            Some(fonts) => fonts.iter().map(String::as_str).collect(),
+            None => self.os.default_fonts().to_vec(),
+        }
+    }
+}
+
+/// Returns the font section of the bootstrap script, empty if the host's
+/// fonts are exposed.
+pub(crate) fn fonts_script(fonts: Option<&[&str]>) -> String {
+    match fonts {
+        Some(fonts) => {
+            FONTS_SCRIPT.replace("__FONTS__", &serde_json::Value::from(fonts).to_string())
+        }
+        None => String::new(),
+    }
+}
+
+const FONTS_SCRIPT: &str = r#"
+                const allowedFonts = __FONTS__;
+                const allowedFontSet = new Set(allowedFonts.map((f) => f.toLowerCase()));
+                const genericFonts = new Set([
+                    'serif', 'sans-serif', 'monospace', 'cursive', 'fantasy', 'system-ui',
+                    'ui-serif', 'ui-sans-serif', 'ui-monospace', 'ui-rounded', 'emoji', 'math',
+                    'fangsong', 'inherit', 'initial', 'unset', 'revert', 'revert-layer',
+                    '-webkit-body', '-apple-system', 'blinkmacsystemfont'
+                ]);
+                const missingFont = '"chaser-missing-font"';
+                // commas outside of quotes separate the families
+                const filterFamilies = (families) => String(families)
+                    .split(/,(?=(?:[^'"]*['"][^'"]*['"])*[^'"]*$)/)
+                    .map((family) => {
+                        const name = family.trim().replace(/^['"]|['"]$/g, '').toLowerCase();
+                        return genericFonts.has(name) || allowedFontSet.has(name) ? family : missingFont;
+                    })
+                    .join(',');
+                // the families of the font shorthand follow the size
+                const fontShorthand = /^((?:.*?[ ])?(?:[0-9.]+(?:px|pt|pc|em|rem|ex|ch|vw|vh|vmin|vmax|in|cm|mm|q|%)|xx-small|x-small|small|medium|large|x-large|xx-large|xxx-large|larger|smaller)(?:[ ]*[/][ ]*[^ ]+)?[ ]+)(.+)$/i;
+                const filterFont = (font) => {
+                    const match = fontShorthand.exec(String(font).trim());
+                    return match ? match[1] + filterFamilies(match[2]) : font;
+                };
+                const wrapSetter = (proto, prop, filter) => {
+                    const desc = proto && Object.getOwnPropertyDescriptor(proto, prop);
+                    if (!desc || !desc.set) return;
+                    Object.defineProperty(proto, prop, {
+                        get: desc.get,
+                        set: native(function(value) { return desc.set.call(this, filter(value)); }, desc.set),
+                        configurable: true,
+                        enumerable: desc.enumerable
+                    });
+                };
+
+                if (typeof CSSStyleDeclaration !== 'undefined') {
+                    wrapSetter(CSSStyleDeclaration.prototype, 'fontFamily', filterFamilies);
+                    wrapSetter(CSSStyleDeclaration.prototype, 'font', filterFont);
+                    const setProperty = CSSStyleDeclaration.prototype.setProperty;
+                    CSSStyleDeclaration.prototype.setProperty = native(function(name, value) {
+                        const args = [...arguments];
+                        const prop = String(name).toLowerCase();
+                        if (prop === 'font-family') args[1] = filterFamilies(value);
+                        else if (prop === 'font') args[1] = filterFont(value);
+                        return setProperty.apply(this, args);
+                    }, setProperty);
+                }
+                if (typeof CanvasRenderingContext2D !== 'undefined') {
+                    wrapSetter(CanvasRenderingContext2D.prototype, 'font', filterFont);
+                }
+                if (typeof OffscreenCanvasRenderingContext2D !== 'undefined') {
+                    wrapSetter(OffscreenCanvasRenderingContext2D.prototype, 'font', filterFont);
+                }
+                if (typeof FontFaceSet !== 'undefined') {
+                    const check = FontFaceSet.prototype.check;
+                    FontFaceSet.prototype.check = native(function(font) {
+                        const args = [...arguments];
+                        args[0] = filterFont(font);
+                        return check.apply(this, args);
+                    }, check);
+                }
+                if (typeof window !== 'undefined' && typeof window.queryLocalFonts === 'function') {
+                    // keep the permission behaviour of the real API, but only
+                    // report the profile's fonts
+                    const queryLocalFonts = window.queryLocalFonts;
+                    window.queryLocalFonts = native(function() {
+                        return queryLocalFonts.apply(this, arguments).then((real) => {
+                            const byFamily = new Map(real.map((font) => [font.family.toLowerCase(), font]));
+                            return allowedFonts.map((family) => byFamily.get(family.toLowerCase()) || {
+                                family,
+                                fullName: family,
+                                postscriptName: family.replace(/[ ]+/g, ''),
+                                style: 'Regular',
+                                blob: () => Promise.resolve(new Blob([]))
+                            });
+                        });
+                    }, queryLocalFonts);
+                }
+"#;
+
+const WINDOWS_FONTS: &[&str] = &[
+    "Arial",
+    "Arial Black",
+    "Bahnschrift",
+    "Calibri",
+    "Cambria",
+    "Cambria Math",
+    "Candara",
+    "Comic Sans MS",
+    "Consolas",
+    "Constantia",
+    "Corbel",
+    "Courier New",
+    "Ebrima",
+    "Franklin Gothic Medium",
+    "Gabriola",
+    "Gadugi",
+    "Georgia",
+    "Impact",
+    "Ink Free",
+    "Javanese Text",
+    "Leelawadee UI",
+    "Lucida Console",
+    "Lucida Sans Unicode",
+    "Malgun Gothic",
+    "Marlett",
+    "Microsoft Himalaya",
+    "Microsoft JhengHei",
+    "Microsoft New Tai Lue",
+    "Microsoft PhagsPa",
+    "Microsoft Sans Serif",
+    "Microsoft Tai Le",
+    "Microsoft YaHei",
+    "Microsoft Yi Baiti",
+    "MingLiU-ExtB",
+    "Mongolian Baiti",
+    "MS Gothic",
+    "MV Boli",
+    "Myanmar Text",
+    "Nirmala UI",
+    "Palatino Linotype",
+    "Segoe MDL2 Assets",
+    "Segoe Print",
+    "Segoe Script",
+    "Segoe UI",
+    "Segoe UI Emoji",
+    "Segoe UI Historic",
+    "Segoe UI Symbol",
+    "SimSun",
+    "Sitka",
+    "Sylfaen",
+    "Symbol",
+    "Tahoma",
+    "Times New Roman",
+    "Trebuchet MS",
+    "Verdana",
+    "Webdings",
+    "Wingdings",
+    "Yu Gothic",
+];
+
+const MACOS_FONTS: &[&str] = &[
+    "American Typewriter",
+    "Andale Mono",
+    "Apple Color Emoji",
+    "Apple SD Gothic Neo",
+    "Apple Symbols",
+    "Arial",
+    "Arial Black",
+    "Arial Hebrew",
+    "Arial Narrow",
+    "Arial Rounded MT Bold",
+    "Arial Unicode MS",
+    "Avenir",
+    "Avenir Next",
+    "Avenir Next Condensed",
+    "Baskerville",
+    "Big Caslon",
+    "Bodoni 72",
+    "Bradley Hand",
+    "Brush Script MT",
+    "Chalkboard",
+    "Chalkboard SE",
+    "Chalkduster",
+    "Charter",
+    "Cochin",
+    "Comic Sans MS",
+    "Copperplate",
+    "Courier",
+    "Courier New",
+    "Didot",
+    "DIN Alternate",
+    "DIN Condensed",
+    "Futura",
+    "Geneva",
+    "Georgia",
+    "Gill Sans",
+    "Helvetica",
+    "Helvetica Neue",
+    "Herculanum",
+    "Hiragino Sans",
+    "Hoefler Text",
+    "Impact",
+    "Lucida Grande",
+    "Luminari",
+    "Marker Felt",
+    "Menlo",
+    "Microsoft Sans Serif",
+    "Monaco",
+    "Noteworthy",
+    "Optima",
+    "Palatino",
+    "Papyrus",
+    "Phosphate",
+    "PingFang SC",
+    "Rockwell",
+    "Savoye LET",
+    "SignPainter",
+    "Skia",
+    "Snell Roundhand",
+    "Tahoma",
+    "Times",
+    "Times New Roman",
+    "Trattatello",
+    "Trebuchet MS",
+    "Verdana",
+    "Zapfino",
+];
+
+const LINUX_FONTS: &[&str] = &[
+    "DejaVu Sans",
+    "DejaVu Sans Mono",
+    "DejaVu Serif",
+    "FreeMono",
+    "FreeSans",
+    "FreeSerif",
+    "Liberation Mono",
+    "Liberation Sans",
+    "Liberation Sans Narrow",
+    "Liberation Serif",
+    "Noto Color Emoji",
+    "Noto Mono",
+    "Noto Sans",
+    "Noto Serif",
+    "Ubuntu",
+    "Ubuntu Condensed",
+    "Ubuntu Mono",
+];
+
+const ANDROID_FONTS: &[&str] = &[
+    "Carrois Gothic SC",
+    "Coming Soon",
+    "Cutive Mono",
+    "Dancing Script",
+    "Droid Sans Mono",
+    "Noto Color Emoji",
+    "Noto Sans",
+    "Noto Serif",
+    "Roboto",
+    "Roboto Condensed",
+    "Source Sans Pro",
+];
+
+const IOS_FONTS: &[&str] = &[
+    "Academy Engraved LET",
+    "American Typewriter",
+    "Apple Color Emoji",
+    "Arial",
+    "Arial Hebrew",
+    "Avenir",
+    "Avenir Next",
+    "Baskerville",
+    "Bodoni 72",
+    "Bradley Hand",
+    "Chalkboard SE",
+    "Chalkduster",
+    "Charter",
+    "Cochin",
+    "Copperplate",
+    "Courier",
+    "Courier New",
+    "Didot",
+    "Futura",
+    "Georgia",
+    "Gill Sans",
+    "Helvetica",
+    "Helvetica Neue",
+    "Hiragino Sans",
+    "Hoefler Text",
+    "Marker Felt",
+    "Menlo",
+    "Noteworthy",
+    "Optima",
+    "Palatino",
+    "Papyrus",
+    "Party LET",
+    "PingFang SC",
+    "Rockwell",
+    "Savoye LET",
+    "Snell Roundhand",
+    "Times New Roman",
+    "Trebuchet MS",
+    "Verdana",
+    "Zapfino",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fonts_follow_the_os() {
+        let windows = ChaserProfile::windows().build();
+        assert!(windows.fonts().contains(&"Segoe UI"));
+        assert!(!windows.fonts().contains(&"DejaVu Sans"));
+        assert!(fonts_script(Some(&windows.fonts())).contains(r#""Segoe UI""#));
+
+        let custom = ChaserProfile::linux().fonts(["Ubuntu", "Inter"]).build();
+        assert_eq!(custom.fonts(), ["Ubuntu", "Inter"]);
+    }
+}
@@ -0,0 +1,383 @@
+//! Loading and saving [`ChaserProfile`]s as JSON or TOML files.
+//!
+//! A profile file only needs an `os`; every other field falls back to the
+//! defaults of [`ChaserProfile::new`] for that OS:
+//!
+//! ```toml
+//! os = "Windows"
+//! chrome_version = 130
+//! gpu = "NvidiaRTX4080"
+//! locale = "de-DE"
+//! timezone = "Europe/Berlin"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{
+    Battery, ChaserProfile, Gpu, MediaDevices, NetworkConnection, Os, SpeechVoice, WebRtcPolicy,
+};
+
+/// Errors that occur while loading or saving profile files
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("failed to access profile {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("invalid JSON profile {}: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("invalid TOML profile {}: {source}", path.display())]
+    Toml {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error("failed to serialize profile {}: {message}", path.display())]
+    Serialize { path: PathBuf, message: String },
+    #[error("unsupported profile format {} (expected .json or .toml)", .0.display())]
+    UnsupportedFormat(PathBuf),
+}
+
+/// The on-disk representation of a profile, everything but the OS is optional.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ProfileSpec {
+    os: Os,
+    chrome_version: Option<u32>,
+    gpu: Option<Gpu>,
+    memory_gb: Option<u32>,
+    cpu_cores: Option<u32>,
+    locale: Option<String>,
+    timezone: Option<String>,
+    screen_width: Option<u32>,
+    screen_height: Option<u32>,
+    device_scale_factor: Option<f64>,
+    model: Option<String>,
+    user_agent: Option<String>,
+    noise: Option<bool>,
+    noise_seed: Option<u32>,
+    fonts: Option<Vec<String>>,
+    webrtc_policy: Option<WebRtcPolicy>,
+    languages: Option<Vec<String>>,
+    connection: Option<NetworkConnection>,
+    battery: Option<Battery>,
+    media_devices: Option<MediaDevices>,
+    speech_voices: Option<Vec<SpeechVoice>>,
+}
+
+impl From<ProfileSpec> for ChaserProfile {
+    fn from(spec: ProfileSpec) -> Self {
+        let mut builder = ChaserProfile::new(spec.os);
+        if let Some(version) = spec.chrome_version {
+            builder = builder.chrome_version(version);
+        }
+        if let Some(gpu) = spec.gpu {
+            builder = builder.gpu(gpu);
+        }
+        if let Some(gb) = spec.memory_gb {
+            builder = builder.memory_gb(gb);
+        }
+        if let Some(cores) = spec.cpu_cores {
+            builder = builder.cpu_cores(cores);
+        }
+        if let Some(locale) = spec.locale {
+            builder = builder.locale(locale);
+        }
+        if let Some(tz) = spec.timezone {
+            builder = builder.timezone(tz);
+        }
+        if let Some(factor) = spec.device_scale_factor {
+            builder = builder.device_scale_factor(factor);
+        }
+        if let Some(model) = spec.model {
+            builder = builder.model(model);
+        }
+        if let Some(enabled) = spec.noise {
+            builder = builder.noise(enabled);
+        }
+        if let Some(seed) = spec.noise_seed {
+            builder = builder.noise_seed(seed);
+        }
+        if let Some(fonts) = spec.fonts {
+            builder = builder.fonts(fonts);
+        }
+        if let Some(policy) = spec.webrtc_policy {
+            builder = builder.webrtc_policy(policy);
+        }
+        if let Some(languages) = spec.languages {
+            builder = builder.languages(languages);
+        }
+        if let Some(connection) = spec.connection {
+            builder = builder.connection(connection);
+        }
+        if let Some(battery) = spec.battery {
+            builder = builder.battery(battery);
+        }
+        if let Some(devices) = spec.media_devices {
+            builder = builder.media_devices(devices);
+        }
+        if let Some(voices) = spec.speech_voices {
+            builder = builder.speech_voices(voices);
+        }
+        let width = spec.screen_width.unwrap_or(builder.screen_width);
+        let height = spec.screen_height.unwrap_or(builder.screen_height);
+        let profile = builder.screen(width, height).build();
+        match spec.user_agent {
+            Some(user_agent) => profile.with_user_agent(user_agent),
+            None => profile,
+        }
+    }
+}
+
+/// The file formats a profile can be stored in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Toml,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "toml" => Some(Format::Toml),
+            _ => None,
+        }
+    }
+}
+
+impl ChaserProfile {
+    /// Parse a profile from a JSON string
+    pub fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Parse a profile from a TOML string
+    pub fn from_toml_str(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    /// Serialize this profile as pretty-printed JSON
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Serialize this profile as TOML
+    pub fn to_toml_string(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// Load a profile from a `.json` or `.toml` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        let path = path.as_ref();
+        let format = Format::from_path(path)
+            .ok_or_else(|| ProfileError::UnsupportedFormat(path.to_path_buf()))?;
+        let content = fs::read_to_string(path).map_err(|source| ProfileError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        match format {
+            Format::Json => Self::from_json_str(&content).map_err(|source| ProfileError::Json {
+                path: path.to_path_buf(),
+                source,
+            }),
+            Format::Toml => Self::from_toml_str(&content).map_err(|source| ProfileError::Toml {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    /// Save this profile to a `.json` or `.toml` file, the format is picked
+    /// from the file extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ProfileError> {
+        let path = path.as_ref();
+        let content = match Format::from_path(path) {
+            Some(Format::Json) => self.to_json_string().map_err(|e| e.to_string()),
+            Some(Format::Toml) => self.to_toml_string().map_err(|e| e.to_string()),
+            None => return Err(ProfileError::UnsupportedFormat(path.to_path_buf())),
+        }
+        .map_err(|message| ProfileError::Serialize {
+            path: path.to_path_buf(),
+            message,
+        })?;
+        fs::write(path, content).map_err(|source| ProfileError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// A named collection of profiles loaded from a directory.
+///
+/// Every `.json` and `.toml` file in the directory becomes one profile, named
+/// after its file stem. Other files are ignored.
+///
+/// # Example
+///
+/// ```no_run
+/// use chaser_oxide::profiles::ProfileLibrary;
+///
+/// let library = ProfileLibrary::load_dir("profiles").unwrap();
+/// let profile = library.get("berlin-gamer").unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProfileLibrary {
+    profiles: BTreeMap<String, ChaserProfile>,
+}
+
+impl ProfileLibrary {
+    /// Create an empty library
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load all profiles in `dir`.
+    ///
+    /// Fails on the first file that cannot be read or parsed, so a broken
+    /// profile is never silently skipped.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        let dir = dir.as_ref();
+        let io_err = |source| ProfileError::Io {
+            path: dir.to_path_buf(),
+            source,
+        };
+
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).map_err(io_err)? {
+            let path = entry.map_err(io_err)?.path();
+            if path.is_file() && Format::from_path(&path).is_some() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut library = Self::new();
+        for path in paths {
+            let profile = ChaserProfile::load(&path)?;
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                library.insert(name, profile);
+            }
+        }
+        Ok(library)
+    }
+
+    /// Save every profile to `dir` as `<name>.<extension>`, where `extension`
+    /// is either `json` or `toml`.
+    pub fn save_dir(&self, dir: impl AsRef<Path>, extension: &str) -> Result<(), ProfileError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|source| ProfileError::Io {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+        for (name, profile) in &self.profiles {
+            profile.save(dir.join(format!("{name}.{extension}")))?;
+        }
+        Ok(())
+    }
+
+    /// Add a profile under the given name, returning the one it replaced
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        profile: ChaserProfile,
+    ) -> Option<ChaserProfile> {
+        self.profiles.insert(name.into(), profile)
+    }
+
+    /// Returns the profile with the given name
+    pub fn get(&self, name: &str) -> Option<&ChaserProfile> {
+        self.profiles.get(name)
+    }
+
+    /// Returns the names of all profiles in sorted order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Iterate over all `(name, profile)` pairs in sorted order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ChaserProfile)> {
+        self.profiles.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_json_and_toml() {
+        let profile = ChaserProfile::windows()
+            .gpu(Gpu::NvidiaRTX4080)
+            .locale("de-DE")
+            .timezone("Europe/Berlin")
+            .screen(2560, 1440)
+            .build();
+
+        let json = profile.to_json_string().unwrap();
+        assert_eq!(ChaserProfile::from_json_str(&json).unwrap(), profile);
+
+        let toml = profile.to_toml_string().unwrap();
+        assert_eq!(ChaserProfile::from_toml_str(&toml).unwrap(), profile);
+    }
+
+    #[test]
+    fn roundtrip_custom_user_agent() {
+        let ua = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 \
+                  (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36 Custom/1.0";
+        let profile = ChaserProfile::from_user_agent(ua);
+
+        let json = profile.to_json_string().unwrap();
+        let loaded = ChaserProfile::from_json_str(&json).unwrap();
+        assert_eq!(loaded, profile);
+        assert_eq!(loaded.user_agent(), ua);
+
+        let toml = profile.to_toml_string().unwrap();
+        let loaded = ChaserProfile::from_toml_str(&toml).unwrap();
+        assert_eq!(loaded, profile);
+        assert_eq!(loaded.user_agent(), ua);
+
+        let generated = ChaserProfile::windows().build().to_json_string().unwrap();
+        assert!(!generated.contains("user_agent"), "{generated}");
+    }
+
+    #[test]
+    fn missing_fields_use_os_defaults() {
+        let profile = ChaserProfile::from_toml_str(r#"os = "MacOSArm""#).unwrap();
+        assert_eq!(profile, ChaserProfile::new(Os::MacOSArm).build());
+    }
+
+    #[test]
+    fn unknown_gpu_and_os_are_rejected() {
+        let err = ChaserProfile::from_json_str(r#"{"os": "Windows", "gpu": "Voodoo2"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown variant `Voodoo2`"), "{err}");
+        assert!(err.contains("NvidiaRTX3080"), "{err}");
+
+        let err = ChaserProfile::from_toml_str(r#"os = "BeOS""#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown variant `BeOS`"), "{err}");
+    }
+}
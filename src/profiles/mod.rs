@@ -0,0 +1,1143 @@
+//! Stealth profile system for customizable browser fingerprints.
+//!
+//! This module provides an ergonomic builder pattern for creating consistent
+//! browser "personalities" that bypass anti-bot detection.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chaser-oxide::profiles::{ChaserProfile, Gpu};
+//!
+//! let profile = ChaserProfile::windows()
+//!     .chrome_version(130)
+//!     .gpu(Gpu::NvidiaRTX4080)
+//!     .memory_gb(16)
+//!     .cpu_cores(12)
+//!     .build();
+//! ```
+//!
+//! Profiles can also be kept on disk as JSON or TOML and loaded with
+//! [`ChaserProfile::load`] or, for a whole directory, [`ProfileLibrary`].
+
+use chromiumoxide_cdp::cdp::browser_protocol::emulation::{
+    UserAgentBrandVersion, UserAgentMetadata,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::evasions::EvasionSet;
+use crate::handler::emulation::MAX_TOUCH_POINTS;
+use crate::handler::viewport::Viewport;
+
+mod devices;
+mod fonts;
+mod library;
+mod noise;
+mod random;
+mod validate;
+mod webgl;
+mod webrtc;
+
+pub use devices::{Battery, MediaDevices, NetworkConnection, SpeechVoice};
+pub(crate) use fonts::fonts_script;
+pub use library::{ProfileError, ProfileLibrary};
+pub(crate) use noise::{fnv1a, noise_script};
+pub use random::ProfileDistribution;
+pub use validate::ProfileIssue;
+pub use webgl::{ShaderPrecision, WebGl2Limits, WebGlCapabilities};
+pub(crate) use webrtc::webrtc_script;
+pub use webrtc::WebRtcPolicy;
+
+/// GPU presets for WebGL spoofing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Gpu {
+    /// NVIDIA GeForce RTX 3080 (high-trust gaming GPU)
+    NvidiaRTX3080,
+    /// NVIDIA GeForce RTX 4080 (newer gaming GPU)
+    NvidiaRTX4080,
+    /// NVIDIA GeForce GTX 1660 (mid-range GPU)
+    NvidiaGTX1660,
+    /// Intel UHD Graphics 630 (common laptop GPU)
+    IntelUHD630,
+    /// Intel Iris Xe (modern laptop GPU)
+    IntelIrisXe,
+    /// Apple M1 Pro
+    AppleM1Pro,
+    /// Apple M2 Max
+    AppleM2Max,
+    /// Apple M4 Max
+    AppleM4Max,
+    /// AMD Radeon RX 6800
+    AmdRadeonRX6800,
+    /// Intel UHD Graphics 630 on an Intel Mac (OpenGL)
+    IntelUHD630Mac,
+    /// AMD Radeon Pro 5500M on an Intel MacBook Pro (OpenGL)
+    AmdRadeonPro5500M,
+    /// NVIDIA GeForce GTX 1660 with the proprietary Linux driver (OpenGL)
+    NvidiaGTX1660Linux,
+    /// Intel UHD Graphics 630 with the Mesa Linux driver (OpenGL)
+    MesaIntelUHD630,
+    /// AMD Radeon RX 6800 with the Mesa Linux driver (OpenGL)
+    MesaAmdRadeonRX6800,
+    /// Qualcomm Adreno 740 (Snapdragon 8 Gen 2 Android phones)
+    QualcommAdreno740,
+    /// ARM Mali-G710 (Pixel 7)
+    ArmMaliG710,
+    /// The generic GPU WebKit reports on iPhones
+    AppleGpu,
+}
+
+impl Gpu {
+    /// Every GPU preset
+    pub const ALL: [Gpu; 17] = [
+        Gpu::NvidiaRTX3080,
+        Gpu::NvidiaRTX4080,
+        Gpu::NvidiaGTX1660,
+        Gpu::IntelUHD630,
+        Gpu::IntelIrisXe,
+        Gpu::AppleM1Pro,
+        Gpu::AppleM2Max,
+        Gpu::AppleM4Max,
+        Gpu::AmdRadeonRX6800,
+        Gpu::IntelUHD630Mac,
+        Gpu::AmdRadeonPro5500M,
+        Gpu::NvidiaGTX1660Linux,
+        Gpu::MesaIntelUHD630,
+        Gpu::MesaAmdRadeonRX6800,
+        Gpu::QualcommAdreno740,
+        Gpu::ArmMaliG710,
+        Gpu::AppleGpu,
+    ];
+
+    /// Returns the WebGL vendor string
+    pub fn vendor(&self) -> &'static str {
+        match self {
+            Gpu::NvidiaRTX3080 | Gpu::NvidiaRTX4080 | Gpu::NvidiaGTX1660 => "Google Inc. (NVIDIA)",
+            Gpu::IntelUHD630 | Gpu::IntelIrisXe => "Google Inc. (Intel)",
+            Gpu::AppleM1Pro | Gpu::AppleM2Max | Gpu::AppleM4Max => "Google Inc. (Apple)",
+            Gpu::AmdRadeonRX6800 => "Google Inc. (AMD)",
+            Gpu::IntelUHD630Mac => "Google Inc. (Intel Inc.)",
+            Gpu::AmdRadeonPro5500M => "Google Inc. (ATI Technologies Inc.)",
+            Gpu::NvidiaGTX1660Linux => "Google Inc. (NVIDIA Corporation)",
+            Gpu::MesaIntelUHD630 => "Google Inc. (Intel)",
+            Gpu::MesaAmdRadeonRX6800 => "Google Inc. (AMD)",
+            Gpu::QualcommAdreno740 => "Google Inc. (Qualcomm)",
+            Gpu::ArmMaliG710 => "Google Inc. (ARM)",
+            Gpu::AppleGpu => "Apple Inc.",
+        }
+    }
+
+    /// Returns the WebGL renderer string
+    pub fn renderer(&self) -> &'static str {
+        match self {
+            Gpu::NvidiaRTX3080 => {
+                "ANGLE (NVIDIA, NVIDIA GeForce RTX 3080 Direct3D11 vs_5_0 ps_5_0)"
+            }
+            Gpu::NvidiaRTX4080 => {
+                "ANGLE (NVIDIA, NVIDIA GeForce RTX 4080 Direct3D11 vs_5_0 ps_5_0)"
+            }
+            Gpu::NvidiaGTX1660 => {
+                "ANGLE (NVIDIA, NVIDIA GeForce GTX 1660 SUPER Direct3D11 vs_5_0 ps_5_0)"
+            }
+            Gpu::IntelUHD630 => "ANGLE (Intel, Intel(R) UHD Graphics 630 Direct3D11 vs_5_0 ps_5_0)",
+            Gpu::IntelIrisXe => {
+                "ANGLE (Intel, Intel(R) Iris(R) Xe Graphics Direct3D11 vs_5_0 ps_5_0)"
+            }
+            Gpu::AppleM1Pro => "ANGLE (Apple, Apple M1 Pro, OpenGL 4.1)",
+            Gpu::AppleM2Max => "ANGLE (Apple, Apple M2 Max, OpenGL 4.1)",
+            Gpu::AppleM4Max => {
+                "ANGLE (Apple, ANGLE Metal Renderer: Apple M4 Max, Unspecified Version)"
+            }
+            Gpu::AmdRadeonRX6800 => "ANGLE (AMD, AMD Radeon RX 6800 XT Direct3D11 vs_5_0 ps_5_0)",
+            Gpu::IntelUHD630Mac => "ANGLE (Intel Inc., Intel(R) UHD Graphics 630, OpenGL 4.1)",
+            Gpu::AmdRadeonPro5500M => {
+                "ANGLE (ATI Technologies Inc., AMD Radeon Pro 5500M OpenGL Engine, OpenGL 4.1)"
+            }
+            Gpu::NvidiaGTX1660Linux => {
+                "ANGLE (NVIDIA Corporation, NVIDIA GeForce GTX 1660 SUPER/PCIe/SSE2, OpenGL 4.5.0)"
+            }
+            Gpu::MesaIntelUHD630 => {
+                "ANGLE (Intel, Mesa Intel(R) UHD Graphics 630 (CFL GT2), OpenGL 4.6)"
+            }
+            Gpu::MesaAmdRadeonRX6800 => {
+                "ANGLE (AMD, AMD Radeon RX 6800 XT (radeonsi, navi21, LLVM 15.0.7, DRM 3.54), OpenGL 4.6)"
+            }
+            Gpu::QualcommAdreno740 => "ANGLE (Qualcomm, Adreno (TM) 740, OpenGL ES 3.2)",
+            Gpu::ArmMaliG710 => "ANGLE (ARM, Mali-G710 MC10, OpenGL ES 3.2)",
+            Gpu::AppleGpu => "Apple GPU",
+        }
+    }
+
+    /// Returns the graphics API the renderer string claims ANGLE runs on
+    pub fn backend(&self) -> GraphicsBackend {
+        match self {
+            Gpu::NvidiaRTX3080
+            | Gpu::NvidiaRTX4080
+            | Gpu::NvidiaGTX1660
+            | Gpu::IntelUHD630
+            | Gpu::IntelIrisXe
+            | Gpu::AmdRadeonRX6800 => GraphicsBackend::Direct3D11,
+            Gpu::AppleM4Max | Gpu::AppleGpu => GraphicsBackend::Metal,
+            Gpu::QualcommAdreno740 | Gpu::ArmMaliG710 => GraphicsBackend::OpenGLES,
+            Gpu::AppleM1Pro
+            | Gpu::AppleM2Max
+            | Gpu::IntelUHD630Mac
+            | Gpu::AmdRadeonPro5500M
+            | Gpu::NvidiaGTX1660Linux
+            | Gpu::MesaIntelUHD630
+            | Gpu::MesaAmdRadeonRX6800 => GraphicsBackend::OpenGL,
+        }
+    }
+
+    /// Whether this is an Apple Silicon GPU
+    pub fn is_apple_silicon(&self) -> bool {
+        matches!(self, Gpu::AppleM1Pro | Gpu::AppleM2Max | Gpu::AppleM4Max)
+    }
+
+    /// Whether a real machine running `os` can report this GPU.
+    ///
+    /// The renderer's graphics backend has to be one Chrome uses on that OS,
+    /// and Apple Silicon GPUs only exist on Apple Silicon Macs.
+    pub fn is_compatible_with(&self, os: Os) -> bool {
+        let backend_ok = match os {
+            Os::Windows => self.backend() == GraphicsBackend::Direct3D11,
+            Os::MacOSIntel | Os::MacOSArm => matches!(
+                self.backend(),
+                GraphicsBackend::OpenGL | GraphicsBackend::Metal
+            ),
+            Os::Linux => self.backend() == GraphicsBackend::OpenGL,
+            Os::Android => self.backend() == GraphicsBackend::OpenGLES,
+            Os::Ios => *self == Gpu::AppleGpu,
+        };
+        let hardware_ok = match os {
+            Os::MacOSArm => self.is_apple_silicon(),
+            Os::MacOSIntel => matches!(self, Gpu::IntelUHD630Mac | Gpu::AmdRadeonPro5500M),
+            Os::Windows | Os::Linux | Os::Android | Os::Ios => !self.is_apple_silicon(),
+        };
+        backend_ok && hardware_ok
+    }
+}
+
+/// The graphics API ANGLE translates WebGL calls to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsBackend {
+    /// Windows
+    Direct3D11,
+    /// Linux and older macOS Chrome builds
+    OpenGL,
+    /// Current macOS Chrome builds and iOS
+    Metal,
+    /// Android
+    OpenGLES,
+}
+
+/// Operating system presets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Os {
+    /// Windows 10/11 64-bit
+    Windows,
+    /// macOS (Intel)
+    MacOSIntel,
+    /// macOS (Apple Silicon)
+    MacOSArm,
+    /// Linux x86_64
+    Linux,
+    /// Android phone running Chrome
+    Android,
+    /// iPhone running Chrome (CriOS, which uses WebKit)
+    Ios,
+}
+
+impl Os {
+    /// Returns the navigator.platform value
+    pub fn platform(&self) -> &'static str {
+        match self {
+            Os::Windows => "Win32",
+            Os::MacOSIntel | Os::MacOSArm => "MacIntel",
+            Os::Linux => "Linux x86_64",
+            Os::Android => "Linux armv8l",
+            Os::Ios => "iPhone",
+        }
+    }
+
+    /// Whether this is a mobile OS with a touch screen
+    pub fn is_mobile(&self) -> bool {
+        matches!(self, Os::Android | Os::Ios)
+    }
+
+    /// Returns the client hints platform
+    pub fn hints_platform(&self) -> &'static str {
+        match self {
+            Os::Windows => "Windows",
+            Os::MacOSIntel | Os::MacOSArm => "macOS",
+            Os::Linux => "Linux",
+            Os::Android => "Android",
+            Os::Ios => "iOS",
+        }
+    }
+
+    /// Returns the `Sec-CH-UA-Platform-Version` value.
+    ///
+    /// On Windows this is the UniversalApiContract version (`15.0.0` is
+    /// Windows 11), on macOS the OS version and on Linux the kernel version.
+    pub fn platform_version(&self) -> &'static str {
+        match self {
+            Os::Windows => "15.0.0",
+            Os::MacOSIntel => "13.6.7",
+            Os::MacOSArm => "14.6.1",
+            Os::Linux => "6.5.0",
+            Os::Android => "14.0.0",
+            Os::Ios => "17.6.0",
+        }
+    }
+
+    /// Returns the `Sec-CH-UA-Arch` value, Android leaves it empty
+    pub fn architecture(&self) -> &'static str {
+        match self {
+            Os::MacOSArm | Os::Ios => "arm",
+            Os::Windows | Os::MacOSIntel | Os::Linux => "x86",
+            Os::Android => "",
+        }
+    }
+
+    /// Returns the `Sec-CH-UA-Bitness` value, Android leaves it empty
+    pub fn bitness(&self) -> &'static str {
+        match self {
+            Os::Android => "",
+            _ => "64",
+        }
+    }
+}
+
+/// A builder for creating consistent browser fingerprint profiles.
+///
+/// # Example
+///
+/// ```rust
+/// use chaser-oxide::profiles::{ChaserProfile, Gpu, Os};
+///
+/// // Quick preset
+/// let profile = ChaserProfile::windows().build();
+///
+/// // Customized
+/// let profile = ChaserProfile::new(Os::Windows)
+///     .chrome_version(130)
+///     .gpu(Gpu::NvidiaRTX4080)
+///     .memory_gb(32)
+///     .cpu_cores(16)
+///     .locale("de-DE")
+///     .timezone("Europe/Berlin")
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "library::ProfileSpec")]
+pub struct ChaserProfile {
+    os: Os,
+    chrome_version: u32,
+    gpu: Gpu,
+    memory_gb: u32,
+    cpu_cores: u32,
+    locale: String,
+    timezone: String,
+    screen_width: u32,
+    screen_height: u32,
+    device_scale_factor: f64,
+    model: String,
+    /// Set by [`ChaserProfile::from_user_agent`], replaces the generated UA
+    #[serde(
+        rename = "user_agent",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    custom_user_agent: Option<String>,
+    noise: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    noise_seed: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fonts: Option<Vec<String>>,
+    webrtc_policy: WebRtcPolicy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    languages: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    connection: Option<NetworkConnection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    battery: Option<Battery>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    media_devices: Option<MediaDevices>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speech_voices: Option<Vec<SpeechVoice>>,
+    /// Derived in [`ChaserProfileBuilder::build`] so the [`StealthProfile`]
+    /// impl can hand out references
+    #[serde(skip)]
+    user_agent: String,
+    #[serde(skip)]
+    hint_brands: Vec<(String, String)>,
+}
+
+impl Default for ChaserProfile {
+    fn default() -> Self {
+        Self::windows().build()
+    }
+}
+
+impl ChaserProfile {
+    /// Create a new profile builder with the specified OS
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(os: Os) -> ChaserProfileBuilder {
+        ChaserProfileBuilder {
+            os,
+            chrome_version: 129,
+            gpu: match os {
+                Os::Windows => Gpu::NvidiaRTX3080,
+                Os::MacOSIntel => Gpu::AppleM1Pro,
+                Os::MacOSArm => Gpu::AppleM4Max,
+                Os::Linux => Gpu::NvidiaGTX1660,
+                Os::Android => Gpu::ArmMaliG710,
+                Os::Ios => Gpu::AppleGpu,
+            },
+            memory_gb: 8,
+            cpu_cores: match os {
+                // the default M4 Max ships with 14 or 16 cores
+                Os::MacOSArm => 14,
+                Os::Ios => 6,
+                Os::Windows | Os::MacOSIntel | Os::Linux | Os::Android => 8,
+            },
+            locale: "en-US".to_string(),
+            timezone: "America/New_York".to_string(),
+            screen_width: match os {
+                Os::Android => 412,
+                Os::Ios => 393,
+                _ => 1920,
+            },
+            screen_height: match os {
+                Os::Android => 915,
+                Os::Ios => 852,
+                _ => 1080,
+            },
+            device_scale_factor: match os {
+                Os::Android => 2.625,
+                Os::Ios => 3.0,
+                _ => 1.0,
+            },
+            model: match os {
+                Os::Android => "Pixel 7".to_string(),
+                _ => String::new(),
+            },
+            noise: true,
+            noise_seed: None,
+            fonts: None,
+            webrtc_policy: WebRtcPolicy::Default,
+            languages: None,
+            connection: None,
+            battery: None,
+            media_devices: None,
+            speech_voices: None,
+        }
+    }
+
+    /// Create a Windows profile with sensible defaults (RTX 3080, 8 cores)
+    pub fn windows() -> ChaserProfileBuilder {
+        Self::new(Os::Windows)
+    }
+
+    /// Create a macOS Intel profile.
+    ///
+    /// Keeps reporting the Apple M1 Pro GPU it always has, which
+    /// [`Self::validate`] flags. Pick e.g. [`Gpu::IntelUHD630Mac`] for a
+    /// consistent profile, [`Self::random`] always does.
+    pub fn macos_intel() -> ChaserProfileBuilder {
+        Self::new(Os::MacOSIntel).gpu(Gpu::AppleM1Pro)
+    }
+
+    /// Create a macOS Apple Silicon profile
+    pub fn macos_arm() -> ChaserProfileBuilder {
+        Self::new(Os::MacOSArm).gpu(Gpu::AppleM4Max)
+    }
+
+    /// Create a Linux profile.
+    ///
+    /// Keeps reporting the Direct3D GTX 1660 it always has, which
+    /// [`Self::validate`] flags. Pick e.g. [`Gpu::NvidiaGTX1660Linux`] for a
+    /// consistent profile, [`Self::random`] always does.
+    pub fn linux() -> ChaserProfileBuilder {
+        Self::new(Os::Linux)
+    }
+
+    /// Create an Android profile (Chrome on a Pixel 7)
+    pub fn android() -> ChaserProfileBuilder {
+        Self::new(Os::Android)
+    }
+
+    /// Create an iOS profile (Chrome on an iPhone 15)
+    pub fn ios() -> ChaserProfileBuilder {
+        Self::new(Os::Ios)
+    }
+
+    /// Creates a profile that reports `user_agent` verbatim.
+    ///
+    /// The OS and Chrome major version are read from the string so the client
+    /// hints, `navigator.userAgentData` and `navigator.platform` tell the same
+    /// story. Unrecognized parts fall back to the Windows defaults.
+    pub fn from_user_agent(user_agent: &str) -> ChaserProfile {
+        let os = if user_agent.contains("iPhone") || user_agent.contains("iPad") {
+            Os::Ios
+        } else if user_agent.contains("Android") {
+            Os::Android
+        } else if user_agent.contains("Macintosh") {
+            Os::MacOSIntel
+        } else if user_agent.contains("Linux") || user_agent.contains("X11") {
+            Os::Linux
+        } else {
+            Os::Windows
+        };
+        let mut builder = Self::new(os);
+        let version = ["Chrome/", "CriOS/"].iter().find_map(|token| {
+            let (_, rest) = user_agent.split_once(token)?;
+            let major = rest.split(|c: char| !c.is_ascii_digit()).next()?;
+            major.parse().ok()
+        });
+        if let Some(version) = version {
+            builder = builder.chrome_version(version);
+        }
+        builder.build().with_user_agent(user_agent.to_string())
+    }
+
+    /// Replace the generated user agent with `user_agent`, verbatim
+    pub(crate) fn with_user_agent(mut self, user_agent: String) -> Self {
+        self.user_agent = user_agent.clone();
+        self.custom_user_agent = Some(user_agent);
+        self
+    }
+
+    // Getters
+    pub fn os(&self) -> Os {
+        self.os
+    }
+    pub fn chrome_version(&self) -> u32 {
+        self.chrome_version
+    }
+    pub fn gpu(&self) -> Gpu {
+        self.gpu
+    }
+    pub fn memory_gb(&self) -> u32 {
+        self.memory_gb
+    }
+    pub fn cpu_cores(&self) -> u32 {
+        self.cpu_cores
+    }
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+    pub fn timezone(&self) -> &str {
+        &self.timezone
+    }
+    pub fn screen_width(&self) -> u32 {
+        self.screen_width
+    }
+    pub fn screen_height(&self) -> u32 {
+        self.screen_height
+    }
+    pub fn device_scale_factor(&self) -> f64 {
+        self.device_scale_factor
+    }
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+    pub fn webrtc_policy(&self) -> WebRtcPolicy {
+        self.webrtc_policy
+    }
+
+    /// Whether this profile emulates a mobile device
+    pub fn is_mobile(&self) -> bool {
+        self.os.is_mobile()
+    }
+
+    /// Returns the `navigator.maxTouchPoints` value
+    pub fn max_touch_points(&self) -> u32 {
+        if self.is_mobile() {
+            MAX_TOUCH_POINTS as u32
+        } else {
+            0
+        }
+    }
+
+    /// Returns the viewport a mobile profile is emulated with: the full
+    /// portrait screen with touch. Desktop profiles keep the window size.
+    pub(crate) fn mobile_viewport(&self) -> Option<Viewport> {
+        self.is_mobile().then_some(Viewport {
+            width: self.screen_width,
+            height: self.screen_height,
+            device_scale_factor: Some(self.device_scale_factor),
+            emulating_mobile: true,
+            is_landscape: self.screen_width > self.screen_height,
+            has_touch: true,
+        })
+    }
+
+    /// Returns the User-Agent string for this profile
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    fn build_user_agent(&self) -> String {
+        let os_part = match self.os {
+            Os::Windows => "Windows NT 10.0; Win64; x64",
+            Os::MacOSIntel | Os::MacOSArm => "Macintosh; Intel Mac OS X 10_15_7",
+            Os::Linux => "X11; Linux x86_64",
+            Os::Android => {
+                return format!(
+                    "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{}.0.0.0 Mobile Safari/537.36",
+                    self.chrome_version
+                )
+            }
+            Os::Ios => {
+                return format!(
+                    "Mozilla/5.0 (iPhone; CPU iPhone OS 17_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/{} Mobile/15E148 Safari/604.1",
+                    self.chrome_full_version()
+                )
+            }
+        };
+        format!(
+            "Mozilla/5.0 ({}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{}.0.0.0 Safari/537.36",
+            os_part, self.chrome_version
+        )
+    }
+
+    /// Returns the full Chrome version reported in `Sec-CH-UA-Full-Version-List`.
+    ///
+    /// Known major versions map to a real stable build, others fall back to
+    /// the reduced `<major>.0.0.0` form.
+    pub fn chrome_full_version(&self) -> String {
+        let build = match self.chrome_version {
+            120 => "0.6099.216",
+            121 => "0.6167.184",
+            122 => "0.6261.128",
+            123 => "0.6312.122",
+            124 => "0.6367.207",
+            125 => "0.6422.141",
+            126 => "0.6478.126",
+            127 => "0.6533.119",
+            128 => "0.6613.137",
+            129 => "0.6668.100",
+            130 => "0.6723.116",
+            131 => "0.6778.85",
+            132 => "0.6834.110",
+            133 => "0.6943.126",
+            134 => "0.6998.165",
+            135 => "0.7049.114",
+            136 => "0.7103.113",
+            137 => "0.7151.119",
+            138 => "0.7204.157",
+            _ => "0.0.0",
+        };
+        format!("{}.{}", self.chrome_version, build)
+    }
+
+    /// Returns the `Sec-CH-UA` brand list.
+    ///
+    /// The GREASE brand and the order of the entries are derived from the
+    /// major version the same way Chrome does it, so the list matches what a
+    /// real browser of that version sends.
+    pub fn brands(&self) -> Vec<UserAgentBrandVersion> {
+        self.brand_list(false)
+    }
+
+    /// Returns the `Sec-CH-UA-Full-Version-List` brand list
+    pub fn full_version_list(&self) -> Vec<UserAgentBrandVersion> {
+        self.brand_list(true)
+    }
+
+    fn brand_list(&self, full_version: bool) -> Vec<UserAgentBrandVersion> {
+        const GREASE_CHARS: [char; 11] = [' ', '(', ':', '-', '.', '/', ')', ';', '=', '?', '_'];
+        const GREASE_VERSIONS: [&str; 3] = ["8", "99", "24"];
+        const ORDERS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        let seed = self.chrome_version as usize;
+        let grease = UserAgentBrandVersion::new(
+            format!(
+                "Not{}A{}Brand",
+                GREASE_CHARS[seed % 11],
+                GREASE_CHARS[(seed + 1) % 11]
+            ),
+            match full_version {
+                true => format!("{}.0.0.0", GREASE_VERSIONS[seed % 3]),
+                false => GREASE_VERSIONS[seed % 3].to_string(),
+            },
+        );
+        let version = match full_version {
+            true => self.chrome_full_version(),
+            false => self.chrome_version.to_string(),
+        };
+        let chromium = UserAgentBrandVersion::new("Chromium", version.clone());
+        let chrome = UserAgentBrandVersion::new("Google Chrome", version);
+
+        let order = ORDERS[seed % 6];
+        let mut brands = vec![None, None, None];
+        brands[order[0]] = Some(grease);
+        brands[order[1]] = Some(chromium);
+        brands[order[2]] = Some(chrome);
+        brands.into_iter().flatten().collect()
+    }
+
+    /// Returns the client hints metadata for `setUserAgentOverride`, which
+    /// drives both the `Sec-CH-UA*` request headers and `navigator.userAgentData`.
+    ///
+    /// `None` for iOS, where Chrome runs on WebKit and sends no client hints.
+    pub fn user_agent_metadata(&self) -> Option<UserAgentMetadata> {
+        if self.os == Os::Ios {
+            return None;
+        }
+        let form_factor = if self.is_mobile() {
+            "Mobile"
+        } else {
+            "Desktop"
+        };
+        Some(UserAgentMetadata {
+            brands: Some(self.brands()),
+            full_version_list: Some(self.full_version_list()),
+            platform: self.os.hints_platform().to_string(),
+            platform_version: self.os.platform_version().to_string(),
+            architecture: self.os.architecture().to_string(),
+            model: self.model.clone(),
+            mobile: self.is_mobile(),
+            bitness: Some(self.os.bitness().to_string()),
+            wow64: Some(false),
+            form_factors: Some(vec![form_factor.to_string()]),
+        })
+    }
+
+    /// Returns the `navigator.languages` list for this profile's locale.
+    ///
+    /// A regional locale is followed by its base language, and non-English
+    /// locales fall back to `en-US`/`en` like a stock Chrome install does.
+    /// [`ChaserProfileBuilder::languages`] replaces the derived list.
+    pub fn languages(&self) -> Vec<String> {
+        if let Some(languages) = &self.languages {
+            return languages.clone();
+        }
+        let mut languages = vec![self.locale.clone()];
+        let base = self.locale.split(['-', '_']).next().unwrap_or_default();
+        if !base.is_empty() && base != self.locale {
+            languages.push(base.to_string());
+        }
+        if base != "en" {
+            languages.push("en-US".to_string());
+            languages.push("en".to_string());
+        }
+        languages
+    }
+
+    /// Returns the `Accept-Language` header value matching [`Self::languages`]
+    pub fn accept_language(&self) -> String {
+        StealthProfile::accept_language(self)
+    }
+
+    /// Returns the `screen.availHeight` value, accounting for the OS taskbar/menu bar
+    pub fn screen_avail_height(&self) -> u32 {
+        let reserved = match self.os {
+            Os::Windows => 40,
+            Os::MacOSIntel | Os::MacOSArm => 25,
+            Os::Linux | Os::Android | Os::Ios => 0,
+        };
+        self.screen_height.saturating_sub(reserved)
+    }
+
+    /// Generate the complete JavaScript bootstrap script for this profile
+    pub fn bootstrap_script(&self) -> String {
+        EvasionSet::default().script(self)
+    }
+}
+
+impl fmt::Display for ChaserProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ChaserProfile({:?}, Chrome {}, {:?})",
+            self.os, self.chrome_version, self.gpu
+        )
+    }
+}
+
+/// Builder for constructing `ChaserProfile` instances
+#[derive(Debug, Clone)]
+pub struct ChaserProfileBuilder {
+    os: Os,
+    chrome_version: u32,
+    gpu: Gpu,
+    memory_gb: u32,
+    cpu_cores: u32,
+    locale: String,
+    timezone: String,
+    screen_width: u32,
+    screen_height: u32,
+    device_scale_factor: f64,
+    model: String,
+    noise: bool,
+    noise_seed: Option<u32>,
+    fonts: Option<Vec<String>>,
+    webrtc_policy: WebRtcPolicy,
+    languages: Option<Vec<String>>,
+    connection: Option<NetworkConnection>,
+    battery: Option<Battery>,
+    media_devices: Option<MediaDevices>,
+    speech_voices: Option<Vec<SpeechVoice>>,
+}
+
+impl ChaserProfileBuilder {
+    /// Set the Chrome version (default: 129)
+    pub fn chrome_version(mut self, version: u32) -> Self {
+        self.chrome_version = version;
+        self
+    }
+
+    /// Set the GPU for WebGL spoofing
+    pub fn gpu(mut self, gpu: Gpu) -> Self {
+        self.gpu = gpu;
+        self
+    }
+
+    /// Set device memory in GB (default: 8)
+    pub fn memory_gb(mut self, gb: u32) -> Self {
+        self.memory_gb = gb;
+        self
+    }
+
+    /// Set CPU core count (default: 8)
+    pub fn cpu_cores(mut self, cores: u32) -> Self {
+        self.cpu_cores = cores;
+        self
+    }
+
+    /// Set the locale (e.g., "en-US", "de-DE")
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = locale.into();
+        self
+    }
+
+    /// Set the timezone (e.g., "America/New_York", "Europe/Berlin")
+    pub fn timezone(mut self, tz: impl Into<String>) -> Self {
+        self.timezone = tz.into();
+        self
+    }
+
+    /// Set screen resolution
+    pub fn screen(mut self, width: u32, height: u32) -> Self {
+        self.screen_width = width;
+        self.screen_height = height;
+        self
+    }
+
+    /// Set the device pixel ratio (default: 1 on desktop, the device's ratio on mobile)
+    pub fn device_scale_factor(mut self, factor: f64) -> Self {
+        self.device_scale_factor = factor;
+        self
+    }
+
+    /// Set the device model reported in `Sec-CH-UA-Model` (mobile only)
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Enable or disable canvas, WebGL readback and audio noise (default: enabled)
+    pub fn noise(mut self, enabled: bool) -> Self {
+        self.noise = enabled;
+        self
+    }
+
+    /// Set the seed of the canvas, WebGL readback and audio noise. By default
+    /// it is derived from the other profile values.
+    pub fn noise_seed(mut self, seed: u32) -> Self {
+        self.noise_seed = Some(seed);
+        self
+    }
+
+    /// Set the font families the profile exposes (default: the fonts of a
+    /// stock installation of the OS, see [`Os::default_fonts`])
+    pub fn fonts<I, S>(mut self, fonts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fonts = Some(fonts.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set how WebRTC exposes IP addresses (default: [`WebRtcPolicy::Default`]).
+    ///
+    /// The page side is handled by the profile script. Launch the browser
+    /// with the same policy through
+    /// [`BrowserConfigBuilder::webrtc_policy`](crate::browser::BrowserConfigBuilder::webrtc_policy)
+    /// so Chrome doesn't gather the candidates in the first place.
+    pub fn webrtc_policy(mut self, policy: WebRtcPolicy) -> Self {
+        self.webrtc_policy = policy;
+        self
+    }
+
+    /// Set the `navigator.languages` list (default: derived from the locale,
+    /// see [`ChaserProfile::languages`]). The first entry is also
+    /// `navigator.language`.
+    pub fn languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.languages = Some(languages.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set `navigator.connection` (default: see [`Os::default_connection`])
+    pub fn connection(mut self, connection: NetworkConnection) -> Self {
+        self.connection = Some(connection);
+        self
+    }
+
+    /// Set the state `navigator.getBattery()` resolves with (default: see
+    /// [`Os::default_battery`])
+    pub fn battery(mut self, battery: Battery) -> Self {
+        self.battery = Some(battery);
+        self
+    }
+
+    /// Set the devices `navigator.mediaDevices.enumerateDevices()` lists
+    /// (default: see [`Os::default_media_devices`])
+    pub fn media_devices(mut self, devices: MediaDevices) -> Self {
+        self.media_devices = Some(devices);
+        self
+    }
+
+    /// Set the voices of `speechSynthesis.getVoices()` (default: see
+    /// [`Os::default_speech_voices`])
+    pub fn speech_voices(mut self, voices: impl IntoIterator<Item = SpeechVoice>) -> Self {
+        self.speech_voices = Some(voices.into_iter().collect());
+        self
+    }
+
+    /// Build the final profile
+    pub fn build(self) -> ChaserProfile {
+        let mut profile = ChaserProfile {
+            os: self.os,
+            chrome_version: self.chrome_version,
+            gpu: self.gpu,
+            memory_gb: self.memory_gb,
+            cpu_cores: self.cpu_cores,
+            locale: self.locale,
+            timezone: self.timezone,
+            screen_width: self.screen_width,
+            screen_height: self.screen_height,
+            device_scale_factor: self.device_scale_factor,
+            model: self.model,
+            custom_user_agent: None,
+            noise: self.noise,
+            noise_seed: self.noise_seed,
+            fonts: self.fonts,
+            webrtc_policy: self.webrtc_policy,
+            languages: self.languages,
+            connection: self.connection,
+            battery: self.battery,
+            media_devices: self.media_devices,
+            speech_voices: self.speech_voices,
+            user_agent: String::new(),
+            hint_brands: Vec::new(),
+        };
+        profile.user_agent = profile.build_user_agent();
+        profile.hint_brands = profile
+            .brands()
+            .into_iter()
+            .map(|b| (b.brand, b.version))
+            .collect();
+        profile
+    }
+}
+
+impl StealthProfile for ChaserProfile {
+    fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+    fn platform(&self) -> &str {
+        self.os.platform()
+    }
+    fn webgl_vendor(&self) -> &str {
+        self.gpu.vendor()
+    }
+    fn webgl_renderer(&self) -> &str {
+        self.gpu.renderer()
+    }
+    fn hardware_concurrency(&self) -> u32 {
+        self.cpu_cores
+    }
+    fn device_memory(&self) -> u32 {
+        self.memory_gb
+    }
+    fn client_hints_brands(&self) -> Vec<(&str, &str)> {
+        self.hint_brands
+            .iter()
+            .map(|(brand, version)| (brand.as_str(), version.as_str()))
+            .collect()
+    }
+    fn client_hints_platform(&self) -> &str {
+        self.os.hints_platform()
+    }
+    fn user_agent_metadata(&self) -> Option<UserAgentMetadata> {
+        ChaserProfile::user_agent_metadata(self)
+    }
+    fn languages(&self) -> Vec<String> {
+        ChaserProfile::languages(self)
+    }
+    fn locale(&self) -> Option<&str> {
+        Some(&self.locale)
+    }
+    fn timezone(&self) -> Option<&str> {
+        Some(&self.timezone)
+    }
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        Some((self.screen_width, self.screen_height))
+    }
+    fn screen_avail_size(&self) -> Option<(u32, u32)> {
+        Some((self.screen_width, self.screen_avail_height()))
+    }
+    fn device_scale_factor(&self) -> f64 {
+        self.device_scale_factor
+    }
+    fn mobile_viewport(&self) -> Option<Viewport> {
+        ChaserProfile::mobile_viewport(self)
+    }
+    fn max_touch_points(&self) -> u32 {
+        ChaserProfile::max_touch_points(self)
+    }
+    fn webgl_capabilities(&self) -> Option<WebGlCapabilities> {
+        Some(self.gpu.webgl_capabilities())
+    }
+    fn noise_seed(&self) -> Option<u32> {
+        ChaserProfile::noise_seed(self)
+    }
+    fn fonts(&self) -> Option<Vec<&str>> {
+        Some(ChaserProfile::fonts(self))
+    }
+    fn webrtc_policy(&self) -> WebRtcPolicy {
+        self.webrtc_policy
+    }
+    fn connection(&self) -> Option<NetworkConnection> {
+        ChaserProfile::connection(self)
+    }
+    fn battery(&self) -> Option<Battery> {
+        ChaserProfile::battery(self)
+    }
+    fn media_devices(&self) -> MediaDevices {
+        ChaserProfile::media_devices(self)
+    }
+    fn speech_voices(&self) -> Vec<SpeechVoice> {
+        ChaserProfile::speech_voices(self)
+    }
+    fn bootstrap_script(&self) -> String {
+        EvasionSet::default().script(self)
+    }
+}
+
+// Re-export the old trait-based system for backwards compatibility
+pub use crate::stealth::{LinuxProfile, MacOSProfile, StealthProfile, WindowsNvidiaProfile};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages_follow_locale() {
+        let profile = ChaserProfile::windows().locale("de-DE").build();
+        assert_eq!(profile.languages(), ["de-DE", "de", "en-US", "en"]);
+        assert_eq!(
+            profile.accept_language(),
+            "de-DE,de;q=0.9,en-US;q=0.8,en;q=0.7"
+        );
+
+        let profile = ChaserProfile::windows().build();
+        assert_eq!(profile.languages(), ["en-US", "en"]);
+        assert_eq!(profile.accept_language(), "en-US,en;q=0.9");
+
+        let many = (0..12).map(|i| format!("x{i}")).collect::<Vec<_>>();
+        let profile = ChaserProfile::windows().languages(many).build();
+        assert!(profile
+            .accept_language()
+            .ends_with("x9;q=0.1,x10;q=0.1,x11;q=0.1"));
+    }
+
+    #[test]
+    fn brands_match_chrome() {
+        let brands = |version| {
+            ChaserProfile::windows()
+                .chrome_version(version)
+                .build()
+                .brands()
+                .into_iter()
+                .map(|b| format!("{} {}", b.brand, b.version))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            brands(129),
+            ["Google Chrome 129", "Not=A?Brand 8", "Chromium 129"]
+        );
+        assert_eq!(
+            brands(131),
+            ["Google Chrome 131", "Chromium 131", "Not_A Brand 24"]
+        );
+
+        let metadata = ChaserProfile::macos_arm()
+            .build()
+            .user_agent_metadata()
+            .unwrap();
+        assert_eq!(metadata.platform, "macOS");
+        assert_eq!(metadata.architecture, "arm");
+        assert_eq!(
+            metadata.full_version_list.unwrap()[0].version,
+            "129.0.6668.100"
+        );
+    }
+
+    #[test]
+    fn mobile_profiles() {
+        let android = ChaserProfile::android().build();
+        assert!(android.user_agent().contains("Mobile Safari"));
+        assert_eq!(android.max_touch_points(), 5);
+        assert_eq!(android.os().platform(), "Linux armv8l");
+        let metadata = android.user_agent_metadata().unwrap();
+        assert!(metadata.mobile);
+        assert_eq!(metadata.model, "Pixel 7");
+        assert_eq!(metadata.form_factors.unwrap(), ["Mobile"]);
+
+        let ios = ChaserProfile::ios().build();
+        assert!(ios.user_agent().contains("CriOS/129.0.6668.100"));
+        assert!(ios.user_agent_metadata().is_none());
+        let viewport = ios.mobile_viewport().unwrap();
+        assert!(viewport.emulating_mobile && viewport.has_touch);
+        assert_eq!(viewport.device_scale_factor, Some(3.0));
+
+        assert!(ChaserProfile::windows().build().mobile_viewport().is_none());
+    }
+
+    #[test]
+    fn profiles_from_user_agents() {
+        let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+        let profile = ChaserProfile::from_user_agent(ua);
+        assert_eq!(profile.user_agent(), ua);
+        assert_eq!(profile.os(), Os::MacOSIntel);
+        assert_eq!(profile.chrome_version(), 131);
+        assert_eq!(profile.user_agent_metadata().unwrap().platform, "macOS");
+
+        let android = ChaserProfile::from_user_agent(
+            "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/128.0.0.0 Mobile Safari/537.36",
+        );
+        assert_eq!(android.os(), Os::Android);
+        assert_eq!(android.chrome_version(), 128);
+
+        let unknown = ChaserProfile::from_user_agent("curl/8.0");
+        assert_eq!(unknown.os(), Os::Windows);
+        assert_eq!(unknown.chrome_version(), 129);
+    }
+}
@@ -0,0 +1,195 @@
+//! Seeded noise for canvas, WebGL readback and audio fingerprints.
+//!
+//! Canvas and audio hashes depend on the GPU, driver and audio stack of the
+//! machine rather than on anything a profile spoofs, so every profile running
+//! on the same host would share them. The script returned here perturbs the
+//! read back data with noise derived from the profile's seed: the same profile
+//! always produces the same hash, different profiles produce different ones.
+
+use super::ChaserProfile;
+
+impl ChaserProfile {
+    /// Returns the seed used for canvas, WebGL and audio noise, `None` if
+    /// noise is turned off for this profile.
+    ///
+    /// Without an explicit [`ChaserProfileBuilder::noise_seed`] the seed is
+    /// derived from the profile itself, so equal profiles share their
+    /// fingerprint hashes.
+    ///
+    /// [`ChaserProfileBuilder::noise_seed`]: super::ChaserProfileBuilder::noise_seed
+    pub fn noise_seed(&self) -> Option<u32> {
+        if !self.noise {
+            return None;
+        }
+        Some(self.noise_seed.unwrap_or_else(|| {
+            fnv1a(
+                format!(
+                    "{:?}|{}|{:?}|{}|{}|{}|{}|{}x{}|{}",
+                    self.os,
+                    self.chrome_version,
+                    self.gpu,
+                    self.memory_gb,
+                    self.cpu_cores,
+                    self.locale,
+                    self.timezone,
+                    self.screen_width,
+                    self.screen_height,
+                    self.model,
+                )
+                .as_bytes(),
+            )
+        }))
+    }
+}
+
+/// Returns the noise section of the bootstrap script, empty if noise is
+/// turned off.
+pub(crate) fn noise_script(seed: Option<u32>) -> String {
+    match seed {
+        Some(seed) => NOISE_SCRIPT.replace("__SEED__", &seed.to_string()),
+        None => String::new(),
+    }
+}
+
+/// 32 bit FNV-1a, stable across platforms and Rust versions unlike the
+/// std hashers
+pub(crate) fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// The noise only depends on the seed and the position of a value, so
+/// reading the same content twice yields the same result.
+const NOISE_SCRIPT: &str = r#"
+                const noiseSeed = __SEED__;
+                const noiseAt = (salt, i) => {
+                    let h = Math.imul(noiseSeed ^ salt, 0x9e3779b1) ^ i;
+                    h = Math.imul(h ^ (h >>> 16), 0x85ebca6b);
+                    h = Math.imul(h ^ (h >>> 13), 0xc2b2ae35);
+                    return (h ^ (h >>> 16)) >>> 0;
+                };
+                // flip the lowest bit of one color channel in about every
+                // tenth visible pixel, RGBA layout
+                const noisePixels = (data) => {
+                    for (let p = 0; p * 4 < data.length; p++) {
+                        const r = noiseAt(1, p);
+                        if (r % 10 !== 0 || data[p * 4 + 3] === 0) continue;
+                        data[p * 4 + ((r >>> 8) % 3)] ^= 1;
+                    }
+                };
+
+                if (typeof CanvasRenderingContext2D !== 'undefined') {
+                    const getImageData = CanvasRenderingContext2D.prototype.getImageData;
+                    CanvasRenderingContext2D.prototype.getImageData = native(function() {
+                        const image = getImageData.apply(this, arguments);
+                        noisePixels(image.data);
+                        return image;
+                    }, getImageData);
+
+                    // Serialize a noised copy, the canvas itself stays untouched
+                    const noisedCopy = (canvas) => {
+                        if (!canvas.width || !canvas.height) return canvas;
+                        const copy = document.createElement('canvas');
+                        copy.width = canvas.width;
+                        copy.height = canvas.height;
+                        const ctx = copy.getContext('2d');
+                        ctx.drawImage(canvas, 0, 0);
+                        ctx.putImageData(ctx.getImageData(0, 0, copy.width, copy.height), 0, 0);
+                        return copy;
+                    };
+                    const toDataURL = HTMLCanvasElement.prototype.toDataURL;
+                    HTMLCanvasElement.prototype.toDataURL = native(function() {
+                        return toDataURL.apply(noisedCopy(this), arguments);
+                    }, toDataURL);
+                    const toBlob = HTMLCanvasElement.prototype.toBlob;
+                    HTMLCanvasElement.prototype.toBlob = native(function() {
+                        return toBlob.apply(noisedCopy(this), arguments);
+                    }, toBlob);
+                }
+
+                const noiseReadPixels = (proto) => {
+                    const readPixels = proto.readPixels;
+                    proto.readPixels = native(function() {
+                        const result = readPixels.apply(this, arguments);
+                        const pixels = arguments[6];
+                        if (pixels instanceof Uint8Array || pixels instanceof Uint8ClampedArray) {
+                            noisePixels(pixels);
+                        }
+                        return result;
+                    }, readPixels);
+                };
+                if (typeof WebGLRenderingContext !== 'undefined') {
+                    noiseReadPixels(WebGLRenderingContext.prototype);
+                }
+                if (typeof WebGL2RenderingContext !== 'undefined') {
+                    noiseReadPixels(WebGL2RenderingContext.prototype);
+                }
+
+                if (typeof AudioBuffer !== 'undefined') {
+                    // getChannelData returns the buffer's own storage, so each
+                    // channel is only noised once
+                    const noisedChannels = new WeakMap();
+                    const getChannelData = AudioBuffer.prototype.getChannelData;
+                    AudioBuffer.prototype.getChannelData = native(function(channel) {
+                        const data = getChannelData.apply(this, arguments);
+                        let done = noisedChannels.get(this);
+                        if (!done) {
+                            done = new Set();
+                            noisedChannels.set(this, done);
+                        }
+                        if (!done.has(channel)) {
+                            done.add(channel);
+                            for (let i = 0; i < data.length; i++) {
+                                data[i] += (noiseAt(2 + channel, i) / 0xffffffff - 0.5) * 2e-7;
+                            }
+                        }
+                        return data;
+                    }, getChannelData);
+                    const copyFromChannel = AudioBuffer.prototype.copyFromChannel;
+                    AudioBuffer.prototype.copyFromChannel = native(function(destination, channel) {
+                        this.getChannelData(channel);
+                        return copyFromChannel.apply(this, arguments);
+                    }, copyFromChannel);
+                }
+                if (typeof AnalyserNode !== 'undefined') {
+                    const getFloatFrequencyData = AnalyserNode.prototype.getFloatFrequencyData;
+                    AnalyserNode.prototype.getFloatFrequencyData = native(function(array) {
+                        const result = getFloatFrequencyData.apply(this, arguments);
+                        for (let i = 0; i < array.length; i++) {
+                            array[i] += (noiseAt(16, i) / 0xffffffff - 0.5) * 1e-4;
+                        }
+                        return result;
+                    }, getFloatFrequencyData);
+                }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_is_stable_per_profile() {
+        let windows = ChaserProfile::windows().build();
+        assert_eq!(
+            windows.noise_seed(),
+            ChaserProfile::windows().build().noise_seed()
+        );
+        assert_ne!(
+            windows.noise_seed(),
+            ChaserProfile::windows().cpu_cores(16).build().noise_seed()
+        );
+        assert_eq!(
+            ChaserProfile::windows().noise_seed(7).build().noise_seed(),
+            Some(7)
+        );
+
+        let off = ChaserProfile::windows().noise(false).build();
+        assert_eq!(off.noise_seed(), None);
+        assert!(!off.bootstrap_script().contains("noiseSeed"));
+        assert!(windows.bootstrap_script().contains(&format!(
+            "const noiseSeed = {};",
+            windows.noise_seed().unwrap()
+        )));
+    }
+}
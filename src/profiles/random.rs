@@ -0,0 +1,385 @@
+//! Seeded generation of internally consistent profiles.
+//!
+//! Running many sessions with the same preset makes them trivially
+//! clusterable. [`ProfileDistribution`] samples every hardware and locale
+//! value from weighted market-share tables instead, while only ever
+//! combining values a real machine could report together.
+
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use super::{ChaserProfile, ChaserProfileBuilder, Gpu, Os};
+
+/// The `navigator.deviceMemory` values Chrome reports. Real RAM is rounded
+/// down to a power of two and capped at 8.
+pub(super) const REPORTED_MEMORY_GB: [u32; 4] = [1, 2, 4, 8];
+
+/// Weighted tables used by [`ChaserProfile::random`].
+///
+/// Weights are relative to the other entries of the same table, an entry with
+/// a weight of `0` is never picked. The defaults approximate current desktop
+/// Chrome market share and can be tuned per entry. Android and iOS have
+/// hardware and screen tables but no OS weight, so they are only sampled once
+/// enabled with [`ProfileDistribution::os`]:
+///
+/// ```no_run
+/// use chaser_oxide::profiles::{ProfileDistribution, Os};
+///
+/// let profile = ProfileDistribution::default()
+///     .os(Os::Linux, 0)
+///     .region("de-DE", "Europe/Berlin", 50)
+///     .sample(42)
+///     .build();
+/// ```
+///
+/// Whatever the weights, sampling keeps a profile consistent: the GPU always
+/// matches the OS graphics stack, Apple Silicon core counts follow the chip
+/// and memory is limited to values Chrome would actually report.
+#[derive(Debug, Clone)]
+pub struct ProfileDistribution {
+    os: Vec<(Os, u32)>,
+    gpus: Vec<(Gpu, u32)>,
+    cpu_cores: Vec<(u32, u32)>,
+    memory_gb: Vec<(u32, u32)>,
+    screens: Vec<(Os, (u32, u32), u32)>,
+    regions: Vec<((String, String), u32)>,
+    chrome_versions: Vec<(u32, u32)>,
+}
+
+impl Default for ProfileDistribution {
+    fn default() -> Self {
+        let screens = [
+            (Os::Windows, (1920, 1080), 45),
+            (Os::Windows, (1536, 864), 12),
+            (Os::Windows, (2560, 1440), 12),
+            (Os::Windows, (1366, 768), 10),
+            (Os::Windows, (1600, 900), 5),
+            (Os::Windows, (1440, 900), 4),
+            (Os::Windows, (3840, 2160), 4),
+            (Os::Windows, (1280, 720), 3),
+            (Os::Linux, (1920, 1080), 60),
+            (Os::Linux, (2560, 1440), 20),
+            (Os::Linux, (1366, 768), 10),
+            (Os::Linux, (3840, 2160), 5),
+            (Os::Android, (412, 915), 30),
+            (Os::Android, (360, 800), 25),
+            (Os::Android, (384, 854), 15),
+            (Os::Android, (393, 873), 10),
+            (Os::Ios, (393, 852), 35),
+            (Os::Ios, (390, 844), 30),
+            (Os::Ios, (430, 932), 20),
+            (Os::Ios, (375, 667), 10),
+        ]
+        .into_iter()
+        .chain([Os::MacOSIntel, Os::MacOSArm].into_iter().flat_map(|os| {
+            [
+                (os, (1512, 982), 25),
+                (os, (1440, 900), 15),
+                (os, (1728, 1117), 15),
+                (os, (1920, 1080), 10),
+                (os, (2560, 1440), 10),
+                (os, (1680, 1050), 8),
+                (os, (1280, 800), 5),
+            ]
+        }))
+        .collect();
+
+        let regions = [
+            ("en-US", "America/New_York", 20),
+            ("en-US", "America/Los_Angeles", 12),
+            ("en-US", "America/Chicago", 10),
+            ("en-US", "America/Denver", 3),
+            ("en-GB", "Europe/London", 8),
+            ("de-DE", "Europe/Berlin", 6),
+            ("fr-FR", "Europe/Paris", 5),
+            ("pt-BR", "America/Sao_Paulo", 4),
+            ("es-ES", "Europe/Madrid", 3),
+            ("it-IT", "Europe/Rome", 3),
+            ("ja-JP", "Asia/Tokyo", 3),
+            ("en-CA", "America/Toronto", 3),
+            ("nl-NL", "Europe/Amsterdam", 2),
+            ("pl-PL", "Europe/Warsaw", 2),
+            ("en-AU", "Australia/Sydney", 2),
+        ]
+        .into_iter()
+        .map(|(locale, tz, w)| ((locale.to_string(), tz.to_string()), w))
+        .collect();
+
+        Self {
+            os: vec![
+                (Os::Windows, 70),
+                (Os::MacOSArm, 14),
+                (Os::MacOSIntel, 4),
+                (Os::Linux, 5),
+            ],
+            gpus: vec![
+                (Gpu::IntelIrisXe, 25),
+                (Gpu::IntelUHD630, 20),
+                (Gpu::NvidiaGTX1660, 10),
+                (Gpu::NvidiaRTX3080, 6),
+                (Gpu::AmdRadeonRX6800, 4),
+                (Gpu::NvidiaRTX4080, 3),
+                (Gpu::AppleM1Pro, 50),
+                (Gpu::AppleM2Max, 20),
+                (Gpu::AppleM4Max, 10),
+                (Gpu::IntelUHD630Mac, 70),
+                (Gpu::AmdRadeonPro5500M, 30),
+                (Gpu::MesaIntelUHD630, 50),
+                (Gpu::NvidiaGTX1660Linux, 30),
+                (Gpu::MesaAmdRadeonRX6800, 20),
+                (Gpu::QualcommAdreno740, 60),
+                (Gpu::ArmMaliG710, 40),
+                (Gpu::AppleGpu, 1),
+            ],
+            cpu_cores: vec![(8, 30), (4, 20), (12, 15), (16, 15), (6, 10), (20, 5)],
+            memory_gb: vec![(8, 75), (4, 22), (2, 3)],
+            screens,
+            regions,
+            chrome_versions: vec![(129, 1)],
+        }
+    }
+}
+
+impl ProfileDistribution {
+    /// Set the weight of an operating system
+    pub fn os(mut self, os: Os, weight: u32) -> Self {
+        set_weight(&mut self.os, os, weight);
+        self
+    }
+
+    /// Set the weight of a GPU. It is only picked for OSes it is compatible
+    /// with, see [`Gpu::is_compatible_with`].
+    pub fn gpu(mut self, gpu: Gpu, weight: u32) -> Self {
+        set_weight(&mut self.gpus, gpu, weight);
+        self
+    }
+
+    /// Set the weight of a CPU core count. Apple Silicon profiles ignore this
+    /// table and use the core counts of their chip.
+    pub fn cpu_cores(mut self, cores: u32, weight: u32) -> Self {
+        set_weight(&mut self.cpu_cores, cores, weight);
+        self
+    }
+
+    /// Set the weight of a device memory value. Values Chrome never reports
+    /// (anything but 1, 2, 4 or 8) are ignored when sampling.
+    pub fn memory_gb(mut self, gb: u32, weight: u32) -> Self {
+        set_weight(&mut self.memory_gb, gb, weight);
+        self
+    }
+
+    /// Set the weight of a screen resolution for the given OS
+    pub fn screen(mut self, os: Os, width: u32, height: u32, weight: u32) -> Self {
+        match self
+            .screens
+            .iter_mut()
+            .find(|(o, size, _)| *o == os && *size == (width, height))
+        {
+            Some(entry) => entry.2 = weight,
+            None => self.screens.push((os, (width, height), weight)),
+        }
+        self
+    }
+
+    /// Set the weight of a locale and timezone pair. Both are picked together
+    /// so a profile never claims a locale from a different region than its
+    /// timezone.
+    pub fn region(
+        mut self,
+        locale: impl Into<String>,
+        timezone: impl Into<String>,
+        weight: u32,
+    ) -> Self {
+        set_weight(&mut self.regions, (locale.into(), timezone.into()), weight);
+        self
+    }
+
+    /// Set the weight of a Chrome major version
+    pub fn chrome_version(mut self, version: u32, weight: u32) -> Self {
+        set_weight(&mut self.chrome_versions, version, weight);
+        self
+    }
+
+    /// Sample a profile from a seed. The same seed always yields the same
+    /// profile for a given distribution and crate version.
+    pub fn sample(&self, seed: u64) -> ChaserProfileBuilder {
+        self.sample_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Sample a profile using the given random number generator.
+    ///
+    /// Tables without any usable entry fall back to the defaults of
+    /// [`ChaserProfile::new`], except for the GPU, which falls back to one
+    /// the sampled OS supports.
+    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> ChaserProfileBuilder {
+        let os = pick(rng, self.os.iter().copied()).unwrap_or(Os::Windows);
+        let mut builder = ChaserProfile::new(os);
+
+        let gpu = pick(
+            rng,
+            self.gpus
+                .iter()
+                .copied()
+                .filter(|(gpu, _)| gpu.is_compatible_with(os)),
+        )
+        .or_else(|| {
+            // the macOS Intel and Linux presets keep GPUs their OS can't report
+            std::iter::once(builder.gpu)
+                .chain(Gpu::ALL)
+                .find(|gpu| gpu.is_compatible_with(os))
+        })
+        .unwrap_or(builder.gpu);
+        builder = builder.gpu(gpu);
+
+        // phones keep the core count of their device preset
+        let cores = match apple_silicon_cores(gpu) {
+            Some(cores) => cores.choose(rng).copied(),
+            None if os.is_mobile() => None,
+            None => pick(rng, self.cpu_cores.iter().copied()),
+        };
+        if let Some(cores) = cores {
+            builder = builder.cpu_cores(cores);
+        }
+
+        // every Apple Silicon Mac has at least 8GB, which Chrome reports as 8
+        let memory = if gpu.is_apple_silicon() {
+            Some(8)
+        } else {
+            pick(
+                rng,
+                self.memory_gb
+                    .iter()
+                    .copied()
+                    .filter(|(gb, _)| REPORTED_MEMORY_GB.contains(gb)),
+            )
+        };
+        if let Some(gb) = memory {
+            builder = builder.memory_gb(gb);
+        }
+
+        let screen = pick(
+            rng,
+            self.screens
+                .iter()
+                .filter(|(o, _, _)| *o == os)
+                .map(|(_, size, w)| (*size, *w)),
+        );
+        if let Some((width, height)) = screen {
+            builder = builder.screen(width, height);
+        }
+
+        if let Some((locale, tz)) = pick(rng, self.regions.iter().cloned()) {
+            builder = builder.locale(locale).timezone(tz);
+        }
+
+        if let Some(version) = pick(rng, self.chrome_versions.iter().copied()) {
+            builder = builder.chrome_version(version);
+        }
+
+        // two sessions sampling the same hardware still get distinct noise
+        builder.noise_seed(rng.gen())
+    }
+}
+
+impl ChaserProfile {
+    /// Create a random but internally consistent profile from a seed, using
+    /// the default [`ProfileDistribution`].
+    ///
+    /// The same seed always yields the same profile, so a session can keep its
+    /// identity by storing only the seed.
+    pub fn random(seed: u64) -> ChaserProfileBuilder {
+        ProfileDistribution::default().sample(seed)
+    }
+}
+
+/// Core counts Chrome reports for the Apple Silicon chips
+pub(super) fn apple_silicon_cores(gpu: Gpu) -> Option<&'static [u32]> {
+    match gpu {
+        Gpu::AppleM1Pro => Some(&[8, 10]),
+        Gpu::AppleM2Max => Some(&[12]),
+        Gpu::AppleM4Max => Some(&[14, 16]),
+        _ => None,
+    }
+}
+
+fn set_weight<T: PartialEq>(table: &mut Vec<(T, u32)>, value: T, weight: u32) {
+    match table.iter_mut().find(|(v, _)| *v == value) {
+        Some(entry) => entry.1 = weight,
+        None => table.push((value, weight)),
+    }
+}
+
+/// Pick a value from weighted entries, `None` if no entry has a weight
+fn pick<T, R: Rng + ?Sized>(rng: &mut R, entries: impl Iterator<Item = (T, u32)>) -> Option<T> {
+    let (mut values, weights): (Vec<_>, Vec<_>) = entries.unzip();
+    let index = WeightedIndex::new(&weights).ok()?.sample(rng);
+    Some(values.swap_remove(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::GraphicsBackend;
+
+    #[test]
+    fn same_seed_same_profile() {
+        assert_eq!(
+            ChaserProfile::random(7).build(),
+            ChaserProfile::random(7).build()
+        );
+        let distinct = (0..20)
+            .map(|seed| ChaserProfile::random(seed).build().to_string())
+            .collect::<std::collections::HashSet<_>>();
+        assert!(distinct.len() > 1);
+    }
+
+    #[test]
+    fn random_profiles_are_consistent() {
+        for seed in 0..500 {
+            let profile = ChaserProfile::random(seed).build();
+            let gpu = profile.gpu();
+            assert!(gpu.is_compatible_with(profile.os()), "{profile}");
+            if profile.os() == Os::Windows {
+                assert!(!gpu.is_apple_silicon(), "{profile}");
+            }
+            if profile.os() == Os::Linux {
+                assert_ne!(gpu.backend(), GraphicsBackend::Direct3D11, "{profile}");
+            }
+            assert!(
+                REPORTED_MEMORY_GB.contains(&profile.memory_gb()),
+                "{profile}"
+            );
+            assert_eq!(profile.validate(), [], "{profile}");
+        }
+    }
+
+    #[test]
+    fn zero_gpu_weights_keep_profiles_consistent() {
+        let dist = Gpu::ALL
+            .into_iter()
+            .fold(ProfileDistribution::default(), |dist, gpu| dist.gpu(gpu, 0));
+        for seed in 0..200 {
+            let profile = dist.sample(seed).build();
+            assert_eq!(profile.validate(), [], "{profile}");
+        }
+    }
+
+    #[test]
+    fn tuned_weights_are_respected() {
+        let dist = ProfileDistribution::default()
+            .os(Os::Windows, 0)
+            .os(Os::MacOSArm, 0)
+            .os(Os::MacOSIntel, 0)
+            .memory_gb(16, 100)
+            .memory_gb(8, 0)
+            .memory_gb(4, 0)
+            .memory_gb(2, 0);
+        for seed in 0..50 {
+            let profile = dist.sample(seed).build();
+            assert_eq!(profile.os(), Os::Linux);
+            // 16GB is never reported, so the OS default is kept
+            assert_eq!(profile.memory_gb(), 8);
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Consistency checks for [`ChaserProfile`]s.
+//!
+//! Fingerprinting scripts rarely look at a single value, they look for
+//! combinations no real machine produces. [`ChaserProfile::validate`] reports
+//! those combinations before a profile is used against a target.
+
+use thiserror::Error;
+
+use super::random::{apple_silicon_cores, REPORTED_MEMORY_GB};
+use super::{ChaserProfile, Gpu, Os};
+use crate::browser::Browser;
+
+/// An inconsistency found in a [`ChaserProfile`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ProfileIssue {
+    /// The GPU's renderer string can't come from this OS, e.g. an Apple GPU
+    /// on Windows or a Direct3D renderer on Linux
+    #[error("GPU {gpu:?} ({}) is not available on {os:?}", gpu.renderer())]
+    GpuOsMismatch { gpu: Gpu, os: Os },
+    /// The core count is outside what consumer hardware reports
+    #[error("{cores} CPU cores is not a plausible hardwareConcurrency value")]
+    ImplausibleCpuCores { cores: u32 },
+    /// The core count doesn't exist for the Apple Silicon chip of the profile
+    #[error("{gpu:?} is never paired with {cores} CPU cores")]
+    CpuCoresGpuMismatch { cores: u32, gpu: Gpu },
+    /// Chrome rounds `navigator.deviceMemory` down to a power of two and caps
+    /// it at 8
+    #[error("Chrome never reports {memory_gb}GB as navigator.deviceMemory")]
+    ImplausibleMemory { memory_gb: u32 },
+    /// The screen size is not one a desktop display, or for mobile profiles a
+    /// portrait phone screen, reports
+    #[error("{width}x{height} is not a plausible screen size")]
+    ImplausibleScreen { width: u32, height: u32 },
+    /// The timezone is not used in the locale's region, or is UTC or GMT
+    #[error("timezone {timezone} does not match locale {locale}")]
+    LocaleTimezoneMismatch { locale: String, timezone: String },
+    /// The profile claims a different Chrome major version than the browser
+    /// it runs in, which feature detection exposes
+    #[error("profile claims Chrome {profile} but the browser is Chrome {browser}")]
+    ChromeVersionMismatch { profile: u32, browser: u32 },
+}
+
+impl ChaserProfile {
+    /// Check the profile for values that contradict each other or that no
+    /// real Chrome would report. An empty list means no issue was found.
+    pub fn validate(&self) -> Vec<ProfileIssue> {
+        let mut issues = Vec::new();
+
+        if !self.gpu.is_compatible_with(self.os) {
+            issues.push(ProfileIssue::GpuOsMismatch {
+                gpu: self.gpu,
+                os: self.os,
+            });
+        }
+
+        if !(2..=64).contains(&self.cpu_cores) {
+            issues.push(ProfileIssue::ImplausibleCpuCores {
+                cores: self.cpu_cores,
+            });
+        } else if let Some(cores) = apple_silicon_cores(self.gpu) {
+            if !cores.contains(&self.cpu_cores) {
+                issues.push(ProfileIssue::CpuCoresGpuMismatch {
+                    cores: self.cpu_cores,
+                    gpu: self.gpu,
+                });
+            }
+        }
+
+        if !REPORTED_MEMORY_GB.contains(&self.memory_gb) {
+            issues.push(ProfileIssue::ImplausibleMemory {
+                memory_gb: self.memory_gb,
+            });
+        }
+
+        let screen_ok = if self.os.is_mobile() {
+            (320..=1024).contains(&self.screen_width)
+                && (480..=2048).contains(&self.screen_height)
+                && self.screen_width < self.screen_height
+        } else {
+            (800..=7680).contains(&self.screen_width)
+                && (600..=4320).contains(&self.screen_height)
+                && self.screen_width >= self.screen_height
+        };
+        if !screen_ok {
+            issues.push(ProfileIssue::ImplausibleScreen {
+                width: self.screen_width,
+                height: self.screen_height,
+            });
+        }
+
+        if !timezone_matches_locale(&self.locale, &self.timezone) {
+            issues.push(ProfileIssue::LocaleTimezoneMismatch {
+                locale: self.locale.clone(),
+                timezone: self.timezone.clone(),
+            });
+        }
+
+        issues
+    }
+
+    /// Like [`ChaserProfile::validate`], but also compares the profile against
+    /// the browser it is going to run in.
+    ///
+    /// A profile claiming Chrome 129 on a Chrome 131 binary is caught by any
+    /// script that probes for features of either version.
+    pub async fn validate_against(&self, browser: &Browser) -> crate::Result<Vec<ProfileIssue>> {
+        let mut issues = self.validate();
+        let version = browser.version().await?;
+        if let Some(browser) = parse_major_version(&version.product) {
+            if browser != self.chrome_version {
+                issues.push(ProfileIssue::ChromeVersionMismatch {
+                    profile: self.chrome_version,
+                    browser,
+                });
+            }
+        }
+        Ok(issues)
+    }
+}
+
+/// Parses the major version of a `GetVersionReturns::product`, e.g.
+/// `HeadlessChrome/131.0.6778.85`
+fn parse_major_version(product: &str) -> Option<u32> {
+    product.split('/').nth(1)?.split('.').next()?.parse().ok()
+}
+
+/// Whether `timezone` is used in the region of `locale`. UTC and GMT never
+/// match, no consumer device is set to them, while any other timezone matches
+/// locales without a region or with a region that isn't known here.
+fn timezone_matches_locale(locale: &str, timezone: &str) -> bool {
+    if matches!(timezone, "UTC" | "Etc/UTC" | "GMT" | "Etc/GMT") {
+        return false;
+    }
+    let Some(region) = locale.split(['-', '_']).nth(1) else {
+        return true;
+    };
+    let timezones: &[&str] = match region.to_ascii_uppercase().as_str() {
+        "US" => &[
+            "America/New_York",
+            "America/Chicago",
+            "America/Denver",
+            "America/Los_Angeles",
+            "America/Phoenix",
+            "America/Anchorage",
+            "America/Detroit",
+            "America/Boise",
+            "America/Indiana/Indianapolis",
+            "Pacific/Honolulu",
+        ],
+        "CA" => &[
+            "America/Toronto",
+            "America/Vancouver",
+            "America/Edmonton",
+            "America/Winnipeg",
+            "America/Halifax",
+            "America/Regina",
+            "America/St_Johns",
+        ],
+        "GB" => &["Europe/London"],
+        "IE" => &["Europe/Dublin"],
+        "DE" => &["Europe/Berlin"],
+        "AT" => &["Europe/Vienna"],
+        "CH" => &["Europe/Zurich"],
+        "FR" => &["Europe/Paris"],
+        "ES" => &["Europe/Madrid", "Atlantic/Canary"],
+        "IT" => &["Europe/Rome"],
+        "NL" => &["Europe/Amsterdam"],
+        "BE" => &["Europe/Brussels"],
+        "PL" => &["Europe/Warsaw"],
+        "SE" => &["Europe/Stockholm"],
+        "PT" => &["Europe/Lisbon", "Atlantic/Azores"],
+        "BR" => &[
+            "America/Sao_Paulo",
+            "America/Manaus",
+            "America/Fortaleza",
+            "America/Recife",
+            "America/Bahia",
+        ],
+        "MX" => &[
+            "America/Mexico_City",
+            "America/Monterrey",
+            "America/Tijuana",
+        ],
+        "JP" => &["Asia/Tokyo"],
+        "KR" => &["Asia/Seoul"],
+        "CN" => &["Asia/Shanghai"],
+        "IN" => &["Asia/Kolkata", "Asia/Calcutta"],
+        "AU" => &[
+            "Australia/Sydney",
+            "Australia/Melbourne",
+            "Australia/Brisbane",
+            "Australia/Perth",
+            "Australia/Adelaide",
+            "Australia/Hobart",
+        ],
+        _ => return true,
+    };
+    timezones.contains(&timezone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_consistent() {
+        for profile in [
+            ChaserProfile::windows().build(),
+            ChaserProfile::macos_intel()
+                .gpu(Gpu::IntelUHD630Mac)
+                .build(),
+            ChaserProfile::macos_arm().build(),
+            ChaserProfile::linux().gpu(Gpu::NvidiaGTX1660Linux).build(),
+            ChaserProfile::android().build(),
+            ChaserProfile::ios().build(),
+        ] {
+            assert_eq!(profile.validate(), [], "{profile}");
+        }
+
+        // the historic GPUs of these presets don't exist on their OS
+        for profile in [
+            ChaserProfile::macos_intel().build(),
+            ChaserProfile::linux().build(),
+        ] {
+            assert_eq!(
+                profile.validate(),
+                [ProfileIssue::GpuOsMismatch {
+                    gpu: profile.gpu(),
+                    os: profile.os()
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn detects_inconsistencies() {
+        let issues = ChaserProfile::linux()
+            .gpu(Gpu::NvidiaRTX3080)
+            .memory_gb(32)
+            .locale("de-DE")
+            .timezone("America/New_York")
+            .build()
+            .validate();
+        assert_eq!(
+            issues,
+            [
+                ProfileIssue::GpuOsMismatch {
+                    gpu: Gpu::NvidiaRTX3080,
+                    os: Os::Linux
+                },
+                ProfileIssue::ImplausibleMemory { memory_gb: 32 },
+                ProfileIssue::LocaleTimezoneMismatch {
+                    locale: "de-DE".to_string(),
+                    timezone: "America/New_York".to_string()
+                },
+            ]
+        );
+
+        for locale in ["en", "en-US"] {
+            let issues = ChaserProfile::windows()
+                .locale(locale)
+                .timezone("UTC")
+                .build()
+                .validate();
+            assert_eq!(
+                issues,
+                [ProfileIssue::LocaleTimezoneMismatch {
+                    locale: locale.to_string(),
+                    timezone: "UTC".to_string()
+                }]
+            );
+        }
+        let issues = ChaserProfile::windows()
+            .locale("en")
+            .timezone("Asia/Tokyo")
+            .build()
+            .validate();
+        assert_eq!(issues, []);
+
+        let issues = ChaserProfile::macos_arm()
+            .gpu(Gpu::AppleM1Pro)
+            .cpu_cores(16)
+            .build()
+            .validate();
+        assert_eq!(
+            issues,
+            [ProfileIssue::CpuCoresGpuMismatch {
+                cores: 16,
+                gpu: Gpu::AppleM1Pro
+            }]
+        );
+    }
+
+    #[test]
+    fn mobile_profiles_need_mobile_hardware() {
+        let issues = ChaserProfile::android()
+            .gpu(Gpu::NvidiaRTX3080)
+            .screen(1920, 1080)
+            .build()
+            .validate();
+        assert_eq!(
+            issues,
+            [
+                ProfileIssue::GpuOsMismatch {
+                    gpu: Gpu::NvidiaRTX3080,
+                    os: Os::Android
+                },
+                ProfileIssue::ImplausibleScreen {
+                    width: 1920,
+                    height: 1080
+                },
+            ]
+        );
+        assert!(!Gpu::QualcommAdreno740.is_compatible_with(Os::Linux));
+        assert!(!Gpu::AppleGpu.is_compatible_with(Os::MacOSArm));
+    }
+
+    #[test]
+    fn parses_browser_version() {
+        assert_eq!(
+            parse_major_version("HeadlessChrome/131.0.6778.85"),
+            Some(131)
+        );
+        assert_eq!(parse_major_version("Chrome/129.0.6668.100"), Some(129));
+        assert_eq!(parse_major_version("Chrome"), None);
+    }
+}
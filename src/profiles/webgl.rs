@@ -0,0 +1,703 @@
+//! WebGL capability tables for the [`Gpu`] presets.
+//!
+//! Spoofing only the renderer string is not enough: a profile claiming an
+//! RTX 4080 while `MAX_TEXTURE_SIZE`, the extension list or the shader
+//! precision still come from SwiftShader or the host GPU is easy to spot.
+//! The limits here follow what Chrome reports through ANGLE for each
+//! graphics backend, with per-GPU adjustments where the hardware differs.
+
+use serde_json::{json, Value};
+
+use super::{Gpu, GraphicsBackend};
+
+/// What `getShaderPrecisionFormat` reports for one precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShaderPrecision {
+    pub range_min: i32,
+    pub range_max: i32,
+    pub precision: i32,
+}
+
+impl ShaderPrecision {
+    /// IEEE single precision float
+    const HIGH_FLOAT: Self = Self::new(127, 127, 23);
+    /// 32 bit integer
+    const HIGH_INT: Self = Self::new(31, 30, 0);
+    /// Half precision float on mobile GPUs
+    const HALF_FLOAT: Self = Self::new(15, 15, 10);
+    /// 16 bit integer on mobile GPUs
+    const SHORT_INT: Self = Self::new(15, 14, 0);
+
+    pub const fn new(range_min: i32, range_max: i32, precision: i32) -> Self {
+        Self {
+            range_min,
+            range_max,
+            precision,
+        }
+    }
+}
+
+/// Limits only queryable on a `WebGL2RenderingContext`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebGl2Limits {
+    pub max_3d_texture_size: u32,
+    pub max_array_texture_layers: u32,
+    pub max_color_attachments: u32,
+    pub max_draw_buffers: u32,
+    pub max_samples: u32,
+    pub max_uniform_buffer_bindings: u32,
+    pub max_uniform_block_size: u32,
+    pub max_vertex_uniform_blocks: u32,
+    pub max_fragment_uniform_blocks: u32,
+    pub max_combined_uniform_blocks: u32,
+    pub max_vertex_output_components: u32,
+    pub max_fragment_input_components: u32,
+    pub max_vertex_uniform_components: u32,
+    pub max_fragment_uniform_components: u32,
+    pub max_varying_components: u32,
+    pub max_transform_feedback_separate_attribs: u32,
+    pub max_transform_feedback_separate_components: u32,
+    pub max_transform_feedback_interleaved_components: u32,
+    pub max_element_index: u32,
+    pub max_elements_vertices: u32,
+    pub max_elements_indices: u32,
+    pub max_texture_lod_bias: f32,
+    pub min_program_texel_offset: i32,
+    pub max_program_texel_offset: i32,
+    pub extensions: &'static [&'static str],
+}
+
+/// The WebGL limits, extensions and shader precisions of a GPU
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebGlCapabilities {
+    pub max_texture_size: u32,
+    pub max_renderbuffer_size: u32,
+    pub max_cube_map_texture_size: u32,
+    pub max_viewport_dims: [u32; 2],
+    pub max_vertex_attribs: u32,
+    pub max_vertex_uniform_vectors: u32,
+    pub max_fragment_uniform_vectors: u32,
+    pub max_varying_vectors: u32,
+    pub max_texture_image_units: u32,
+    pub max_vertex_texture_image_units: u32,
+    pub max_combined_texture_image_units: u32,
+    pub aliased_line_width_range: [f32; 2],
+    pub aliased_point_size_range: [f32; 2],
+    pub max_anisotropy: f32,
+    /// Shader precisions as `[low, medium, high]` float and int, the same for
+    /// vertex and fragment shaders
+    pub float_precision: [ShaderPrecision; 3],
+    pub int_precision: [ShaderPrecision; 3],
+    /// Extensions the GPU supports, pages only see the ones the real context
+    /// supports as well
+    pub extensions: &'static [&'static str],
+    pub webgl2: WebGl2Limits,
+}
+
+impl Gpu {
+    /// Returns the WebGL limits Chrome reports for this GPU
+    pub fn webgl_capabilities(&self) -> WebGlCapabilities {
+        let base = match self.backend() {
+            GraphicsBackend::Direct3D11 => d3d11(),
+            GraphicsBackend::OpenGL if self.is_apple_silicon() => WebGlCapabilities {
+                extensions: APPLE_SILICON_WEBGL1,
+                ..mac_opengl()
+            },
+            GraphicsBackend::OpenGL
+                if matches!(self, Gpu::IntelUHD630Mac | Gpu::AmdRadeonPro5500M) =>
+            {
+                mac_opengl()
+            }
+            GraphicsBackend::OpenGL => linux_opengl(),
+            GraphicsBackend::Metal if *self == Gpu::AppleGpu => ios(),
+            GraphicsBackend::Metal => metal(),
+            GraphicsBackend::OpenGLES => android(),
+        };
+
+        match self {
+            Gpu::NvidiaGTX1660Linux => WebGlCapabilities {
+                max_texture_size: 32768,
+                max_renderbuffer_size: 32768,
+                max_cube_map_texture_size: 32768,
+                max_viewport_dims: [32768, 32768],
+                aliased_line_width_range: [1.0, 10.0],
+                aliased_point_size_range: [1.0, 2047.0],
+                ..base
+            },
+            Gpu::MesaAmdRadeonRX6800 => WebGlCapabilities {
+                aliased_point_size_range: [1.0, 8192.0],
+                ..base
+            },
+            Gpu::QualcommAdreno740 => WebGlCapabilities {
+                max_vertex_attribs: 32,
+                max_vertex_uniform_vectors: 256,
+                max_fragment_uniform_vectors: 256,
+                aliased_line_width_range: [1.0, 8.0],
+                aliased_point_size_range: [1.0, 1023.0],
+                ..base
+            },
+            Gpu::IntelUHD630 | Gpu::IntelIrisXe | Gpu::IntelUHD630Mac => WebGlCapabilities {
+                webgl2: WebGl2Limits {
+                    max_samples: 16,
+                    ..base.webgl2
+                },
+                ..base
+            },
+            _ => base,
+        }
+    }
+}
+
+impl WebGlCapabilities {
+    /// Returns the capabilities as the JSON object the bootstrap script reads,
+    /// with `getParameter` values keyed by their GL enum
+    pub(crate) fn to_json(&self) -> Value {
+        let precision = |p: &[ShaderPrecision; 3]| {
+            p.iter()
+                .map(|p| json!([p.range_min, p.range_max, p.precision]))
+                .collect::<Vec<_>>()
+        };
+        let w2 = &self.webgl2;
+        json!({
+            "params": {
+                "3379": self.max_texture_size,
+                "34024": self.max_renderbuffer_size,
+                "34076": self.max_cube_map_texture_size,
+                "3386": self.max_viewport_dims,
+                "34921": self.max_vertex_attribs,
+                "36347": self.max_vertex_uniform_vectors,
+                "36349": self.max_fragment_uniform_vectors,
+                "36348": self.max_varying_vectors,
+                "34930": self.max_texture_image_units,
+                "35660": self.max_vertex_texture_image_units,
+                "35661": self.max_combined_texture_image_units,
+                "33902": self.aliased_line_width_range,
+                "33901": self.aliased_point_size_range,
+                "34047": self.max_anisotropy,
+            },
+            "params2": {
+                "32883": w2.max_3d_texture_size,
+                "35071": w2.max_array_texture_layers,
+                "36063": w2.max_color_attachments,
+                "34852": w2.max_draw_buffers,
+                "36183": w2.max_samples,
+                "35375": w2.max_uniform_buffer_bindings,
+                "35376": w2.max_uniform_block_size,
+                "35371": w2.max_vertex_uniform_blocks,
+                "35373": w2.max_fragment_uniform_blocks,
+                "35374": w2.max_combined_uniform_blocks,
+                "37154": w2.max_vertex_output_components,
+                "37157": w2.max_fragment_input_components,
+                "35658": w2.max_vertex_uniform_components,
+                "35657": w2.max_fragment_uniform_components,
+                "35659": w2.max_varying_components,
+                "35979": w2.max_transform_feedback_separate_attribs,
+                "35968": w2.max_transform_feedback_separate_components,
+                "35978": w2.max_transform_feedback_interleaved_components,
+                "36203": w2.max_element_index,
+                "33000": w2.max_elements_vertices,
+                "33001": w2.max_elements_indices,
+                "34045": w2.max_texture_lod_bias,
+                "35076": w2.min_program_texel_offset,
+                "35077": w2.max_program_texel_offset,
+            },
+            "floatPrecision": precision(&self.float_precision),
+            "intPrecision": precision(&self.int_precision),
+            "extensions": self.extensions,
+            "extensions2": w2.extensions,
+        })
+    }
+}
+
+/// ANGLE on Direct3D 11, the same limits for every Windows GPU
+fn d3d11() -> WebGlCapabilities {
+    WebGlCapabilities {
+        max_texture_size: 16384,
+        max_renderbuffer_size: 16384,
+        max_cube_map_texture_size: 16384,
+        max_viewport_dims: [32767, 32767],
+        max_vertex_attribs: 16,
+        max_vertex_uniform_vectors: 4096,
+        max_fragment_uniform_vectors: 1024,
+        max_varying_vectors: 30,
+        max_texture_image_units: 16,
+        max_vertex_texture_image_units: 16,
+        max_combined_texture_image_units: 32,
+        aliased_line_width_range: [1.0, 1.0],
+        aliased_point_size_range: [1.0, 1024.0],
+        max_anisotropy: 16.0,
+        float_precision: [ShaderPrecision::HIGH_FLOAT; 3],
+        int_precision: [ShaderPrecision::HIGH_INT; 3],
+        extensions: DESKTOP_WEBGL1,
+        webgl2: WebGl2Limits {
+            max_3d_texture_size: 2048,
+            max_array_texture_layers: 2048,
+            max_color_attachments: 8,
+            max_draw_buffers: 8,
+            max_samples: 8,
+            max_uniform_buffer_bindings: 24,
+            max_uniform_block_size: 65536,
+            max_vertex_uniform_blocks: 12,
+            max_fragment_uniform_blocks: 12,
+            max_combined_uniform_blocks: 24,
+            max_vertex_output_components: 120,
+            max_fragment_input_components: 120,
+            max_vertex_uniform_components: 16384,
+            max_fragment_uniform_components: 4096,
+            max_varying_components: 120,
+            max_transform_feedback_separate_attribs: 4,
+            max_transform_feedback_separate_components: 4,
+            max_transform_feedback_interleaved_components: 64,
+            max_element_index: u32::MAX,
+            max_elements_vertices: 2147483647,
+            max_elements_indices: 2147483647,
+            max_texture_lod_bias: 15.0,
+            min_program_texel_offset: -8,
+            max_program_texel_offset: 7,
+            extensions: DESKTOP_WEBGL2,
+        },
+    }
+}
+
+/// ANGLE on the macOS OpenGL 4.1 driver
+fn mac_opengl() -> WebGlCapabilities {
+    let base = d3d11();
+    WebGlCapabilities {
+        max_viewport_dims: [16384, 16384],
+        max_vertex_uniform_vectors: 1024,
+        max_varying_vectors: 15,
+        aliased_point_size_range: [1.0, 255.875],
+        webgl2: WebGl2Limits {
+            max_uniform_block_size: 16384,
+            max_vertex_output_components: 64,
+            max_fragment_input_components: 60,
+            max_vertex_uniform_components: 4096,
+            max_varying_components: 60,
+            max_elements_vertices: 1048575,
+            max_elements_indices: 150000,
+            max_texture_lod_bias: 16.0,
+            ..base.webgl2
+        },
+        ..base
+    }
+}
+
+/// ANGLE on Metal, current Apple Silicon builds
+fn metal() -> WebGlCapabilities {
+    let base = d3d11();
+    WebGlCapabilities {
+        max_viewport_dims: [16384, 16384],
+        max_vertex_uniform_vectors: 1024,
+        max_varying_vectors: 31,
+        aliased_point_size_range: [1.0, 511.0],
+        extensions: APPLE_SILICON_WEBGL1,
+        webgl2: WebGl2Limits {
+            max_samples: 4,
+            max_uniform_block_size: 16384,
+            max_vertex_output_components: 124,
+            max_fragment_input_components: 124,
+            max_vertex_uniform_components: 4096,
+            max_varying_components: 124,
+            max_elements_vertices: 16777216,
+            max_elements_indices: 150000,
+            max_texture_lod_bias: 16.0,
+            extensions: APPLE_SILICON_WEBGL2,
+            ..base.webgl2
+        },
+        ..base
+    }
+}
+
+/// ANGLE on the Mesa OpenGL 4.6 drivers
+fn linux_opengl() -> WebGlCapabilities {
+    let base = d3d11();
+    WebGlCapabilities {
+        max_viewport_dims: [16384, 16384],
+        max_varying_vectors: 31,
+        max_fragment_uniform_vectors: 4096,
+        aliased_line_width_range: [1.0, 7.99],
+        aliased_point_size_range: [1.0, 255.0],
+        webgl2: WebGl2Limits {
+            max_vertex_output_components: 128,
+            max_fragment_input_components: 128,
+            max_fragment_uniform_components: 16384,
+            max_varying_components: 124,
+            max_elements_vertices: 3000,
+            max_elements_indices: 3000,
+            max_texture_lod_bias: 16.0,
+            ..base.webgl2
+        },
+        ..base
+    }
+}
+
+/// ANGLE on the Android OpenGL ES 3.2 drivers
+fn android() -> WebGlCapabilities {
+    WebGlCapabilities {
+        max_texture_size: 8192,
+        max_renderbuffer_size: 8192,
+        max_cube_map_texture_size: 8192,
+        max_viewport_dims: [8192, 8192],
+        max_vertex_attribs: 16,
+        max_vertex_uniform_vectors: 1024,
+        max_fragment_uniform_vectors: 1024,
+        max_varying_vectors: 31,
+        max_texture_image_units: 16,
+        max_vertex_texture_image_units: 16,
+        max_combined_texture_image_units: 32,
+        aliased_line_width_range: [1.0, 100.0],
+        aliased_point_size_range: [1.0, 1024.0],
+        max_anisotropy: 16.0,
+        float_precision: [
+            ShaderPrecision::HALF_FLOAT,
+            ShaderPrecision::HALF_FLOAT,
+            ShaderPrecision::HIGH_FLOAT,
+        ],
+        int_precision: [
+            ShaderPrecision::SHORT_INT,
+            ShaderPrecision::SHORT_INT,
+            ShaderPrecision::HIGH_INT,
+        ],
+        extensions: MOBILE_WEBGL1,
+        webgl2: WebGl2Limits {
+            max_3d_texture_size: 2048,
+            max_array_texture_layers: 2048,
+            max_color_attachments: 8,
+            max_draw_buffers: 8,
+            max_samples: 4,
+            max_uniform_buffer_bindings: 36,
+            max_uniform_block_size: 65536,
+            max_vertex_uniform_blocks: 12,
+            max_fragment_uniform_blocks: 12,
+            max_combined_uniform_blocks: 24,
+            max_vertex_output_components: 124,
+            max_fragment_input_components: 124,
+            max_vertex_uniform_components: 4096,
+            max_fragment_uniform_components: 4096,
+            max_varying_components: 124,
+            max_transform_feedback_separate_attribs: 4,
+            max_transform_feedback_separate_components: 4,
+            max_transform_feedback_interleaved_components: 64,
+            max_element_index: u32::MAX,
+            max_elements_vertices: 2147483647,
+            max_elements_indices: 2147483647,
+            max_texture_lod_bias: 15.0,
+            min_program_texel_offset: -8,
+            max_program_texel_offset: 7,
+            extensions: MOBILE_WEBGL2,
+        },
+    }
+}
+
+/// WebKit on the iPhone GPU
+fn ios() -> WebGlCapabilities {
+    let base = android();
+    WebGlCapabilities {
+        max_texture_size: 16384,
+        max_renderbuffer_size: 16384,
+        max_cube_map_texture_size: 16384,
+        max_viewport_dims: [16384, 16384],
+        aliased_line_width_range: [1.0, 1.0],
+        aliased_point_size_range: [1.0, 511.0],
+        extensions: IOS_WEBGL1,
+        webgl2: WebGl2Limits {
+            max_uniform_buffer_bindings: 24,
+            max_uniform_block_size: 16384,
+            max_texture_lod_bias: 16.0,
+            extensions: IOS_WEBGL2,
+            ..base.webgl2
+        },
+        ..base
+    }
+}
+
+const DESKTOP_WEBGL1: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "EXT_blend_minmax",
+    "EXT_clip_control",
+    "EXT_color_buffer_half_float",
+    "EXT_depth_clamp",
+    "EXT_disjoint_timer_query",
+    "EXT_float_blend",
+    "EXT_frag_depth",
+    "EXT_polygon_offset_clamp",
+    "EXT_shader_texture_lod",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_sRGB",
+    "KHR_parallel_shader_compile",
+    "OES_element_index_uint",
+    "OES_fbo_render_mipmap",
+    "OES_standard_derivatives",
+    "OES_texture_float",
+    "OES_texture_float_linear",
+    "OES_texture_half_float",
+    "OES_texture_half_float_linear",
+    "OES_vertex_array_object",
+    "WEBGL_blend_func_extended",
+    "WEBGL_color_buffer_float",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_depth_texture",
+    "WEBGL_draw_buffers",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+];
+
+const DESKTOP_WEBGL2: &[&str] = &[
+    "EXT_clip_control",
+    "EXT_color_buffer_float",
+    "EXT_color_buffer_half_float",
+    "EXT_conservative_depth",
+    "EXT_depth_clamp",
+    "EXT_disjoint_timer_query_webgl2",
+    "EXT_float_blend",
+    "EXT_polygon_offset_clamp",
+    "EXT_render_snorm",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_texture_norm16",
+    "KHR_parallel_shader_compile",
+    "NV_shader_noperspective_interpolation",
+    "OES_draw_buffers_indexed",
+    "OES_sample_variables",
+    "OES_shader_multisample_interpolation",
+    "OES_texture_float_linear",
+    "OVR_multiview2",
+    "WEBGL_blend_func_extended",
+    "WEBGL_clip_cull_distance",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+    "WEBGL_provoking_vertex",
+    "WEBGL_stencil_texturing",
+];
+
+const APPLE_SILICON_WEBGL1: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "EXT_blend_minmax",
+    "EXT_clip_control",
+    "EXT_color_buffer_half_float",
+    "EXT_depth_clamp",
+    "EXT_disjoint_timer_query",
+    "EXT_float_blend",
+    "EXT_frag_depth",
+    "EXT_polygon_offset_clamp",
+    "EXT_shader_texture_lod",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_sRGB",
+    "KHR_parallel_shader_compile",
+    "OES_element_index_uint",
+    "OES_fbo_render_mipmap",
+    "OES_standard_derivatives",
+    "OES_texture_float",
+    "OES_texture_float_linear",
+    "OES_texture_half_float",
+    "OES_texture_half_float_linear",
+    "OES_vertex_array_object",
+    "WEBGL_blend_func_extended",
+    "WEBGL_color_buffer_float",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_compressed_texture_pvrtc",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_depth_texture",
+    "WEBGL_draw_buffers",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+    "WEBKIT_WEBGL_compressed_texture_pvrtc",
+];
+
+const APPLE_SILICON_WEBGL2: &[&str] = &[
+    "EXT_clip_control",
+    "EXT_color_buffer_float",
+    "EXT_color_buffer_half_float",
+    "EXT_conservative_depth",
+    "EXT_depth_clamp",
+    "EXT_disjoint_timer_query_webgl2",
+    "EXT_float_blend",
+    "EXT_polygon_offset_clamp",
+    "EXT_render_snorm",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_texture_norm16",
+    "KHR_parallel_shader_compile",
+    "NV_shader_noperspective_interpolation",
+    "OES_draw_buffers_indexed",
+    "OES_sample_variables",
+    "OES_shader_multisample_interpolation",
+    "OES_texture_float_linear",
+    "WEBGL_blend_func_extended",
+    "WEBGL_clip_cull_distance",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_compressed_texture_pvrtc",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+    "WEBGL_provoking_vertex",
+    "WEBGL_stencil_texturing",
+    "WEBKIT_WEBGL_compressed_texture_pvrtc",
+];
+
+const MOBILE_WEBGL1: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "EXT_blend_minmax",
+    "EXT_color_buffer_half_float",
+    "EXT_disjoint_timer_query",
+    "EXT_float_blend",
+    "EXT_frag_depth",
+    "EXT_shader_texture_lod",
+    "EXT_texture_filter_anisotropic",
+    "EXT_sRGB",
+    "KHR_parallel_shader_compile",
+    "OES_element_index_uint",
+    "OES_fbo_render_mipmap",
+    "OES_standard_derivatives",
+    "OES_texture_float",
+    "OES_texture_float_linear",
+    "OES_texture_half_float",
+    "OES_texture_half_float_linear",
+    "OES_vertex_array_object",
+    "WEBGL_color_buffer_float",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_depth_texture",
+    "WEBGL_draw_buffers",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+];
+
+const MOBILE_WEBGL2: &[&str] = &[
+    "EXT_color_buffer_float",
+    "EXT_color_buffer_half_float",
+    "EXT_disjoint_timer_query_webgl2",
+    "EXT_float_blend",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_norm16",
+    "KHR_parallel_shader_compile",
+    "OES_draw_buffers_indexed",
+    "OES_sample_variables",
+    "OES_shader_multisample_interpolation",
+    "OES_texture_float_linear",
+    "OVR_multiview2",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+];
+
+const IOS_WEBGL1: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "EXT_blend_minmax",
+    "EXT_clip_control",
+    "EXT_color_buffer_half_float",
+    "EXT_depth_clamp",
+    "EXT_float_blend",
+    "EXT_frag_depth",
+    "EXT_polygon_offset_clamp",
+    "EXT_shader_texture_lod",
+    "EXT_sRGB",
+    "EXT_texture_filter_anisotropic",
+    "KHR_parallel_shader_compile",
+    "OES_element_index_uint",
+    "OES_fbo_render_mipmap",
+    "OES_standard_derivatives",
+    "OES_texture_float",
+    "OES_texture_half_float",
+    "OES_texture_half_float_linear",
+    "OES_vertex_array_object",
+    "WEBGL_color_buffer_float",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_compressed_texture_pvrtc",
+    "WEBKIT_WEBGL_compressed_texture_pvrtc",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_depth_texture",
+    "WEBGL_draw_buffers",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+];
+
+const IOS_WEBGL2: &[&str] = &[
+    "EXT_clip_control",
+    "EXT_color_buffer_float",
+    "EXT_color_buffer_half_float",
+    "EXT_conservative_depth",
+    "EXT_depth_clamp",
+    "EXT_float_blend",
+    "EXT_polygon_offset_clamp",
+    "EXT_render_snorm",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_norm16",
+    "KHR_parallel_shader_compile",
+    "OES_draw_buffers_indexed",
+    "OES_sample_variables",
+    "OES_shader_multisample_interpolation",
+    "WEBGL_clip_cull_distance",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_compressed_texture_pvrtc",
+    "WEBKIT_WEBGL_compressed_texture_pvrtc",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+    "WEBGL_provoking_vertex",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capabilities_follow_the_backend() {
+        let rtx = Gpu::NvidiaRTX4080.webgl_capabilities();
+        assert_eq!(rtx.max_texture_size, 16384);
+        assert_eq!(rtx.max_viewport_dims, [32767, 32767]);
+        assert!(rtx.extensions.contains(&"WEBGL_compressed_texture_s3tc"));
+
+        let mali = Gpu::ArmMaliG710.webgl_capabilities();
+        assert_eq!(mali.float_precision[1], ShaderPrecision::HALF_FLOAT);
+        assert!(!mali.extensions.contains(&"WEBGL_compressed_texture_s3tc"));
+
+        let json = Gpu::AppleM4Max.webgl_capabilities().to_json();
+        assert_eq!(json["params"]["3379"], 16384);
+        assert_eq!(json["params2"]["36183"], 4);
+        assert_eq!(json["floatPrecision"][2], json!([127, 127, 23]));
+    }
+}
@@ -0,0 +1,161 @@
+//! WebRTC IP leak protection.
+//!
+//! ICE candidates carry the host's LAN and public addresses, which bypass any
+//! HTTP proxy the browser is configured with. [`WebRtcPolicy`] restricts what
+//! Chrome gathers with `--force-webrtc-ip-handling-policy` (see
+//! [`BrowserConfigBuilder::webrtc_policy`]) and the profile script rewrites
+//! whatever still reaches the page.
+//!
+//! [`BrowserConfigBuilder::webrtc_policy`]: crate::browser::BrowserConfigBuilder::webrtc_policy
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+/// How WebRTC exposes IP addresses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebRtcPolicy {
+    /// Chrome's default behaviour, host addresses are hidden behind mDNS names
+    /// but the public address is reachable through STUN
+    #[default]
+    Default,
+    /// Remove `RTCPeerConnection` and only allow proxied connections
+    Disabled,
+    /// Only relay candidates, all traffic goes through the proxy
+    ProxyOnly,
+    /// Report this address, usually the proxy's exit IP, in every candidate
+    Spoofed(IpAddr),
+}
+
+impl WebRtcPolicy {
+    /// Returns the value of Chrome's `--force-webrtc-ip-handling-policy`
+    /// switch, `None` to keep the default policy
+    pub fn ip_handling_policy(&self) -> Option<&'static str> {
+        match self {
+            WebRtcPolicy::Default => None,
+            WebRtcPolicy::Disabled | WebRtcPolicy::ProxyOnly => Some("disable_non_proxied_udp"),
+            WebRtcPolicy::Spoofed(_) => Some("default_public_interface_only"),
+        }
+    }
+}
+
+/// Returns the WebRTC section of the bootstrap script
+pub(crate) fn webrtc_script(policy: WebRtcPolicy) -> String {
+    match policy {
+        WebRtcPolicy::Default => String::new(),
+        WebRtcPolicy::Disabled => DISABLE_SCRIPT.to_string(),
+        WebRtcPolicy::ProxyOnly => CANDIDATE_SCRIPT
+            .replace("__MODE__", "'proxy'")
+            .replace("__IP__", "null"),
+        WebRtcPolicy::Spoofed(ip) => CANDIDATE_SCRIPT
+            .replace("__MODE__", "'spoof'")
+            .replace("__IP__", &format!("'{ip}'")),
+    }
+}
+
+const DISABLE_SCRIPT: &str = r#"
+                for (const name of ['RTCPeerConnection', 'webkitRTCPeerConnection', 'RTCDataChannel', 'RTCIceCandidate', 'RTCSessionDescription']) {
+                    try { delete window[name]; } catch (e) {}
+                }
+"#;
+
+/// Drops non-relay candidates (`proxy`) or replaces their addresses (`spoof`),
+/// both in candidate events and in session descriptions
+const CANDIDATE_SCRIPT: &str = r#"
+                if (typeof RTCPeerConnection !== 'undefined') {
+                    const rtcMode = __MODE__;
+                    const rtcIp = __IP__;
+                    const ipv4 = /(?:[0-9]{1,3}[.]){3}[0-9]{1,3}/g;
+                    const ipv6 = /(?:[0-9a-f]{0,4}:){2,7}[0-9a-f]{0,4}/gi;
+                    const keepCandidate = (candidate) => rtcMode !== 'proxy' || !candidate || candidate.includes(' typ relay');
+                    const rewriteAddress = (address) => {
+                        if (rtcMode !== 'spoof' || !address || address === '0.0.0.0' || address.endsWith('.local')) return address;
+                        return address.replace(ipv6, rtcIp).replace(ipv4, (ip) => (ip === '0.0.0.0' ? ip : rtcIp));
+                    };
+                    const crlf = String.fromCharCode(13, 10);
+                    const rewriteSdp = (sdp) => sdp && sdp
+                        .split(crlf)
+                        .filter((line) => !line.startsWith('a=candidate:') || keepCandidate(line))
+                        .map((line) => (line.startsWith('a=candidate:') || line.startsWith('c=') ? rewriteAddress(line) : line))
+                        .join(crlf);
+                    const wrapGetter = (proto, prop, rewrite) => {
+                        const desc = Object.getOwnPropertyDescriptor(proto, prop);
+                        if (!desc || !desc.get) return;
+                        Object.defineProperty(proto, prop, {
+                            get: native(function() { return rewrite(desc.get.call(this)); }, desc.get),
+                            configurable: true,
+                            enumerable: desc.enumerable
+                        });
+                    };
+                    wrapGetter(RTCIceCandidate.prototype, 'candidate', rewriteAddress);
+                    wrapGetter(RTCIceCandidate.prototype, 'address', rewriteAddress);
+                    wrapGetter(RTCIceCandidate.prototype, 'relatedAddress', rewriteAddress);
+                    wrapGetter(RTCSessionDescription.prototype, 'sdp', rewriteSdp);
+                    const candidateToJSON = RTCIceCandidate.prototype.toJSON;
+                    RTCIceCandidate.prototype.toJSON = native(function() {
+                        const json = candidateToJSON.apply(this, arguments);
+                        json.candidate = rewriteAddress(json.candidate);
+                        return json;
+                    }, candidateToJSON);
+
+                    // candidates the policy hides never reach the page
+                    const wrapListener = (listener) => function(event) {
+                        if (event && event.candidate && !keepCandidate(event.candidate.candidate)) return;
+                        return typeof listener === 'function' ? listener.apply(this, arguments) : listener.handleEvent(event);
+                    };
+                    const listeners = new WeakMap();
+                    const addEventListener = RTCPeerConnection.prototype.addEventListener;
+                    RTCPeerConnection.prototype.addEventListener = native(function(type, listener) {
+                        const args = [...arguments];
+                        if (type === 'icecandidate' && listener) {
+                            if (!listeners.has(listener)) listeners.set(listener, wrapListener(listener));
+                            args[1] = listeners.get(listener);
+                        }
+                        return addEventListener.apply(this, args);
+                    }, addEventListener);
+                    const removeEventListener = RTCPeerConnection.prototype.removeEventListener;
+                    RTCPeerConnection.prototype.removeEventListener = native(function(type, listener) {
+                        const args = [...arguments];
+                        if (type === 'icecandidate' && listeners.has(listener)) args[1] = listeners.get(listener);
+                        return removeEventListener.apply(this, args);
+                    }, removeEventListener);
+                    const onicecandidate = Object.getOwnPropertyDescriptor(RTCPeerConnection.prototype, 'onicecandidate');
+                    const handlers = new WeakMap();
+                    Object.defineProperty(RTCPeerConnection.prototype, 'onicecandidate', {
+                        get: native(function() {
+                            return handlers.has(this) ? handlers.get(this) : onicecandidate.get.call(this);
+                        }, onicecandidate.get),
+                        set: native(function(fn) {
+                            handlers.set(this, fn);
+                            onicecandidate.set.call(this, typeof fn === 'function' ? wrapListener(fn) : fn);
+                        }, onicecandidate.set),
+                        configurable: true,
+                        enumerable: true
+                    });
+                }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::ChaserProfile;
+
+    #[test]
+    fn policy_roundtrips_and_maps_to_chrome() {
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        let profile = ChaserProfile::windows()
+            .webrtc_policy(WebRtcPolicy::Spoofed(ip))
+            .build();
+        let toml = profile.to_toml_string().unwrap();
+        assert_eq!(ChaserProfile::from_toml_str(&toml).unwrap(), profile);
+        assert!(profile
+            .bootstrap_script()
+            .contains("const rtcIp = '203.0.113.7';"));
+
+        assert_eq!(WebRtcPolicy::Default.ip_handling_policy(), None);
+        assert_eq!(
+            WebRtcPolicy::ProxyOnly.ip_handling_policy(),
+            Some("disable_non_proxied_udp")
+        );
+    }
+}
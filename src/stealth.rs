@@ -3,6 +3,21 @@
 //! This module provides a trait-based system for defining browser "personalities"
 //! that can bypass anti-bot detection. The community can contribute new profiles
 //! as Chrome versions and GPU models evolve.
+//!
+//! [`ChaserProfile`](crate::profiles::ChaserProfile) implements the trait as
+//! well, so builtin and custom profiles go through the same evasions.
+
+use chromiumoxide_cdp::cdp::browser_protocol::emulation::{
+    UserAgentBrandVersion, UserAgentMetadata,
+};
+
+use crate::evasions::EvasionSet;
+use crate::handler::viewport::Viewport;
+use crate::keys::KeyboardLayout;
+use crate::profiles::{
+    fnv1a, Battery, Gpu, MediaDevices, NetworkConnection, Os, SpeechVoice, WebGlCapabilities,
+    WebRtcPolicy,
+};
 
 /// A trait for defining a consistent browser fingerprint profile.
 ///
@@ -63,86 +78,175 @@ pub trait StealthProfile: Send + Sync {
         "Windows"
     }
 
-    /// Generate the complete JavaScript bootstrap script
-    fn bootstrap_script(&self) -> String {
-        format!(
-            r#"
-            // === chaser-oxide HARDWARE HARMONY ===
-            // Profile: {ua}
-
-            // 1. Platform alignment (on prototype to avoid getOwnPropertyNames detection)
-            Object.defineProperty(Navigator.prototype, 'platform', {{
-                get: () => '{platform}',
-                configurable: true
-            }});
-
-            // 2. Hardware specs (on prototype)
-            Object.defineProperty(Navigator.prototype, 'hardwareConcurrency', {{
-                get: () => {cores},
-                configurable: true
-            }});
-            Object.defineProperty(Navigator.prototype, 'deviceMemory', {{
-                get: () => {memory},
-                configurable: true
-            }});
-
-            // 3. WebGL spoofing (both contexts)
-            const spoofWebGL = (proto) => {{
-                const getParameter = proto.getParameter;
-                proto.getParameter = function(parameter) {{
-                    if (parameter === 37445) return '{webgl_vendor}';
-                    if (parameter === 37446) return '{webgl_renderer}';
-                    return getParameter.apply(this, arguments);
-                }};
-            }};
-            spoofWebGL(WebGLRenderingContext.prototype);
-            if (typeof WebGL2RenderingContext !== 'undefined') {{
-                spoofWebGL(WebGL2RenderingContext.prototype);
-            }}
-
-            // 4. Client Hints (on prototype)
-            Object.defineProperty(Navigator.prototype, 'userAgentData', {{
-                get: () => ({{
-                    brands: [{brands}],
-                    mobile: false,
-                    platform: "{hints_platform}"
-                }}),
-                configurable: true
-            }});
-
-            // 5. Video codecs (H.264/AAC)
-            const canPlayType = HTMLMediaElement.prototype.canPlayType;
-            HTMLMediaElement.prototype.canPlayType = function(type) {{
-                if (type.includes('avc1')) return 'probably';
-                if (type.includes('mp4a.40')) return 'probably';
-                if (type === 'video/mp4') return 'probably';
-                if (type === 'audio/mp4') return 'probably';
-                return canPlayType.apply(this, arguments);
-            }};
-
-            // 6. WebDriver - set to false (not delete, which makes it undefined)
-            Object.defineProperty(Object.getPrototypeOf(navigator), 'webdriver', {{
-                get: () => false,
-                configurable: true
-            }});
-
-            // 7. window.chrome
-            window.chrome = {{ runtime: {{}} }};
-        "#,
-            ua = self.user_agent(),
-            platform = self.platform(),
-            cores = self.hardware_concurrency(),
-            memory = self.device_memory(),
-            webgl_vendor = self.webgl_vendor(),
-            webgl_renderer = self.webgl_renderer(),
-            brands = self
-                .client_hints_brands()
-                .iter()
-                .map(|(b, v)| format!(r#"{{ brand: "{}", version: "{}" }}"#, b, v))
-                .collect::<Vec<_>>()
-                .join(", "),
-            hints_platform = self.client_hints_platform(),
-        )
+    /// The client hints metadata sent with `Sec-CH-UA*` and exposed as
+    /// `navigator.userAgentData`, `None` for browsers without client hints.
+    ///
+    /// Derived from [`Self::client_hints_brands`] and
+    /// [`Self::client_hints_platform`] for a desktop browser by default.
+    fn user_agent_metadata(&self) -> Option<UserAgentMetadata> {
+        let os = match self.client_hints_platform() {
+            "macOS" => Os::MacOSIntel,
+            "Linux" => Os::Linux,
+            "Android" => Os::Android,
+            _ => Os::Windows,
+        };
+        let brands = self.client_hints_brands();
+        Some(UserAgentMetadata {
+            brands: Some(
+                brands
+                    .iter()
+                    .map(|(brand, version)| UserAgentBrandVersion::new(*brand, *version))
+                    .collect(),
+            ),
+            full_version_list: Some(
+                brands
+                    .iter()
+                    .map(|(brand, version)| {
+                        UserAgentBrandVersion::new(*brand, format!("{version}.0.0.0"))
+                    })
+                    .collect(),
+            ),
+            platform: self.client_hints_platform().to_string(),
+            platform_version: os.platform_version().to_string(),
+            architecture: os.architecture().to_string(),
+            model: String::new(),
+            mobile: false,
+            bitness: Some(os.bitness().to_string()),
+            wow64: Some(false),
+            form_factors: Some(vec!["Desktop".to_string()]),
+        })
+    }
+
+    /// The `navigator.languages` list
+    fn languages(&self) -> Vec<String> {
+        vec!["en-US".to_string(), "en".to_string()]
+    }
+
+    /// The `Accept-Language` header value matching [`Self::languages`]
+    fn accept_language(&self) -> String {
+        self.languages()
+            .iter()
+            .enumerate()
+            .map(|(i, lang)| match i {
+                0 => lang.clone(),
+                // Chrome doesn't go below q=0.1
+                _ => format!("{};q={:.1}", lang, (1.0 - i as f64 / 10.0).max(0.1)),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// The ICU locale to emulate, `None` keeps the browser's
+    fn locale(&self) -> Option<&str> {
+        None
+    }
+
+    /// The IANA timezone to emulate, `None` keeps the browser's
+    fn timezone(&self) -> Option<&str> {
+        None
+    }
+
+    /// The keyboard layout [`ChaserPage`](crate::ChaserPage) types with,
+    /// derived from the locale or else the first language
+    fn keyboard_layout(&self) -> KeyboardLayout {
+        match self.locale() {
+            Some(locale) => KeyboardLayout::from_locale(locale),
+            None => self
+                .languages()
+                .first()
+                .map(|lang| KeyboardLayout::from_locale(lang))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The `screen.width`/`screen.height` values, `None` keeps the real screen
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    /// The `screen.availWidth`/`screen.availHeight` values
+    fn screen_avail_size(&self) -> Option<(u32, u32)> {
+        self.screen_size()
+    }
+
+    /// The `window.devicePixelRatio` value
+    fn device_scale_factor(&self) -> f64 {
+        1.0
+    }
+
+    /// The viewport of an emulated mobile device, `None` for desktop profiles
+    /// which keep the window size
+    fn mobile_viewport(&self) -> Option<Viewport> {
+        None
+    }
+
+    /// The `navigator.maxTouchPoints` value
+    fn max_touch_points(&self) -> u32 {
+        0
+    }
+
+    /// The WebGL limits, extensions and shader precision of the spoofed GPU,
+    /// `None` only spoofs the vendor and renderer strings
+    fn webgl_capabilities(&self) -> Option<WebGlCapabilities> {
+        None
+    }
+
+    /// The seed for canvas, WebGL and audio noise, `None` turns noise off.
+    ///
+    /// Derived from the User-Agent and hardware by default, so each profile
+    /// keeps stable fingerprint hashes.
+    fn noise_seed(&self) -> Option<u32> {
+        Some(fnv1a(
+            format!(
+                "{}|{}|{}|{}",
+                self.user_agent(),
+                self.webgl_renderer(),
+                self.hardware_concurrency(),
+                self.device_memory()
+            )
+            .as_bytes(),
+        ))
+    }
+
+    /// The font families visible to the page, `None` exposes the host's fonts
+    fn fonts(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    /// How WebRTC exposes IP addresses
+    fn webrtc_policy(&self) -> WebRtcPolicy {
+        WebRtcPolicy::Default
+    }
+
+    /// The `navigator.connection` values, `None` removes the API like WebKit
+    fn connection(&self) -> Option<NetworkConnection> {
+        Some(NetworkConnection::default())
+    }
+
+    /// The state `navigator.getBattery()` resolves with, `None` removes the API
+    fn battery(&self) -> Option<Battery> {
+        Some(Battery::plugged_in())
+    }
+
+    /// The devices `navigator.mediaDevices.enumerateDevices()` lists
+    fn media_devices(&self) -> MediaDevices {
+        MediaDevices::default()
+    }
+
+    /// The voices of `speechSynthesis.getVoices()`
+    fn speech_voices(&self) -> Vec<SpeechVoice> {
+        SpeechVoice::google_voices()
+    }
+
+    /// Generate the complete JavaScript bootstrap script.
+    ///
+    /// Every profile shares the default [`EvasionSet`], only the values above
+    /// differ.
+    fn bootstrap_script(&self) -> String
+    where
+        Self: Sized,
+    {
+        EvasionSet::default().script(self)
     }
 }
 
@@ -177,6 +281,10 @@ impl StealthProfile for WindowsNvidiaProfile {
     fn device_memory(&self) -> u32 {
         8
     }
+
+    fn webgl_capabilities(&self) -> Option<WebGlCapabilities> {
+        Some(Gpu::NvidiaRTX3080.webgl_capabilities())
+    }
 }
 
 /// A MacOS profile for users who need to appear as Mac users.
@@ -211,6 +319,10 @@ impl StealthProfile for MacOSProfile {
     fn client_hints_platform(&self) -> &str {
         "macOS"
     }
+
+    fn webgl_capabilities(&self) -> Option<WebGlCapabilities> {
+        Some(Gpu::AppleM1Pro.webgl_capabilities())
+    }
 }
 
 /// A Linux profile for users who need to appear as Linux users.
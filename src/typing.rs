@@ -0,0 +1,486 @@
+//! Human typing behavior for [`ChaserPage`](crate::ChaserPage).
+//!
+//! A [`TypingStyle`] turns text into timed key presses: a speed in words per
+//! minute that varies between sessions, intervals that depend on which
+//! fingers type two consecutive keys, key hold times, bursts separated by
+//! pauses and typos on neighboring keys that get noticed and corrected.
+//!
+//! ```rust,no_run
+//! use chaser_oxide::typing::TypingStyle;
+//! use chaser_oxide::{ChaserPage, Page};
+//!
+//! # async fn run(page: Page) -> anyhow::Result<()> {
+//! let chaser = ChaserPage::new(page).with_typing_style(TypingStyle {
+//!     wpm: 70.0,
+//!     typo_rate: 0.01,
+//!     ..Default::default()
+//! });
+//! chaser.type_text_with_typos("hello world").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::keys::KeyboardLayout;
+
+/// The physical key rows, from the digit row down, as `KeyboardEvent.code`s
+const KEY_ROWS: [&[&str]; 4] = [
+    &[
+        "Backquote",
+        "Digit1",
+        "Digit2",
+        "Digit3",
+        "Digit4",
+        "Digit5",
+        "Digit6",
+        "Digit7",
+        "Digit8",
+        "Digit9",
+        "Digit0",
+        "Minus",
+        "Equal",
+    ],
+    &[
+        "KeyQ",
+        "KeyW",
+        "KeyE",
+        "KeyR",
+        "KeyT",
+        "KeyY",
+        "KeyU",
+        "KeyI",
+        "KeyO",
+        "KeyP",
+        "BracketLeft",
+        "BracketRight",
+        "Backslash",
+    ],
+    &[
+        "KeyA",
+        "KeyS",
+        "KeyD",
+        "KeyF",
+        "KeyG",
+        "KeyH",
+        "KeyJ",
+        "KeyK",
+        "KeyL",
+        "Semicolon",
+        "Quote",
+    ],
+    &[
+        "IntlBackslash",
+        "KeyZ",
+        "KeyX",
+        "KeyC",
+        "KeyV",
+        "KeyB",
+        "KeyN",
+        "KeyM",
+        "Comma",
+        "Period",
+        "Slash",
+    ],
+];
+
+/// The column of the first key of each row, counted from the left pinky's
+/// column
+const ROW_START: [i32; 4] = [-1, 0, 0, -1];
+
+/// The horizontal stagger of each row in key widths
+const ROW_STAGGER: [f64; 4] = [0.0, 0.5, 0.75, 1.25];
+
+/// A key press of a typing plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypingKey {
+    /// Type a character
+    Char(char),
+    /// Delete the character before the caret
+    Backspace,
+}
+
+/// One key press of a typing plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypingStep {
+    pub key: TypingKey,
+    /// How long to wait before pressing the key
+    pub delay: Duration,
+    /// How long to hold the key down
+    pub dwell: Duration,
+}
+
+/// How a person types.
+///
+/// The defaults describe an average typist at 45 WPM who mistypes about one
+/// in 33 characters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypingStyle {
+    /// The speed within a burst in words (5 characters) per minute
+    pub wpm: f64,
+    /// The relative spread of the speed between two texts
+    pub wpm_variance: f64,
+    /// The average time a key is held down
+    pub dwell: Duration,
+    /// The chance of a typo per letter or digit
+    pub typo_rate: f64,
+    /// The most characters typed past a typo before noticing it, never past
+    /// the end of the word
+    pub typo_lookahead: usize,
+    /// The average number of keys typed without a pause
+    pub burst_keys: usize,
+    /// The average pause between two bursts, taken at a word boundary
+    pub burst_pause: Duration,
+}
+
+impl Default for TypingStyle {
+    fn default() -> Self {
+        Self {
+            wpm: 45.0,
+            wpm_variance: 0.15,
+            dwell: Duration::from_millis(90),
+            typo_rate: 0.03,
+            typo_lookahead: 2,
+            burst_keys: 12,
+            burst_pause: Duration::from_millis(450),
+        }
+    }
+}
+
+/// The kinds of typos [`TypingStyle`] makes
+#[derive(Debug, Clone, Copy)]
+enum Typo {
+    /// A neighboring key instead of the intended one
+    Neighbor,
+    /// The next character first
+    Transposition,
+    /// The intended key twice
+    DoublePress,
+}
+
+impl TypingStyle {
+    /// The same style without typos
+    pub fn without_typos(&self) -> Self {
+        Self {
+            typo_rate: 0.0,
+            ..self.clone()
+        }
+    }
+
+    /// Plans the key presses that type `text` on `layout`, including typos
+    /// and their corrections
+    pub fn plan(&self, text: &str, layout: KeyboardLayout) -> Vec<TypingStep> {
+        Planner {
+            style: self,
+            layout,
+            rng: rand::thread_rng(),
+            key_interval: 0.0,
+            previous: None,
+            previous_dwell: 0.0,
+            burst_left: 0,
+            steps: Vec::new(),
+        }
+        .plan(text)
+    }
+}
+
+struct Planner<'a, R> {
+    style: &'a TypingStyle,
+    layout: KeyboardLayout,
+    rng: R,
+    /// The average time between two key presses in ms
+    key_interval: f64,
+    /// The code of the last key pressed
+    previous: Option<&'static str>,
+    previous_dwell: f64,
+    burst_left: i64,
+    steps: Vec<TypingStep>,
+}
+
+impl<R: Rng> Planner<'_, R> {
+    fn plan(mut self, text: &str) -> Vec<TypingStep> {
+        let wpm = self.style.wpm * (1.0 + self.style.wpm_variance * gauss(&mut self.rng));
+        // a word is five characters
+        self.key_interval = 60_000.0 / (wpm.max(5.0) * 5.0);
+        self.burst_left = self.burst_length();
+
+        let chars = text.chars().collect::<Vec<_>>();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if chars[..i].last().is_some_and(|prev| prev.is_whitespace()) {
+                self.maybe_pause();
+            }
+
+            let typo = (c.is_alphanumeric()
+                && self.rng.gen_bool(self.style.typo_rate.clamp(0.0, 1.0)))
+            .then(|| self.typo(&chars[i..]))
+            .flatten();
+            let Some((typed, consumed)) = typo else {
+                self.press(TypingKey::Char(c));
+                i += 1;
+                continue;
+            };
+
+            for c in &typed {
+                self.press(TypingKey::Char(*c));
+            }
+            // keep typing for a moment, but not past the end of the word
+            let rest = &chars[i + consumed..];
+            let word = rest.iter().take_while(|c| !c.is_whitespace()).count();
+            let lookahead = self.rng.gen_range(0..=self.style.typo_lookahead.min(word));
+            for c in &rest[..lookahead] {
+                self.press(TypingKey::Char(*c));
+            }
+
+            // notice, delete and retype
+            let notice = self.rng.gen_range(200.0..500.0);
+            self.pause(notice);
+            for _ in 0..typed.len() + lookahead {
+                self.press(TypingKey::Backspace);
+            }
+            let resume = self.rng.gen_range(80.0..200.0);
+            self.pause(resume);
+            for c in &chars[i..i + consumed] {
+                self.press(TypingKey::Char(*c));
+            }
+            i += consumed;
+        }
+        self.steps
+    }
+
+    /// What gets typed instead of the start of `chars` and how many
+    /// characters of it that covers
+    fn typo(&mut self, chars: &[char]) -> Option<(Vec<char>, usize)> {
+        let c = chars[0];
+        let typo = match self.rng.gen_range(0..10) {
+            0..=5 => Typo::Neighbor,
+            6..=7 => Typo::Transposition,
+            _ => Typo::DoublePress,
+        };
+        match typo {
+            Typo::Neighbor => self.neighbor(c).map(|n| (vec![n], 1)),
+            Typo::Transposition => match chars.get(1) {
+                Some(&next) if next.is_alphanumeric() && next != c => Some((vec![next, c], 2)),
+                _ => None,
+            },
+            Typo::DoublePress => Some((vec![c, c], 1)),
+        }
+    }
+
+    /// A random character on a key next to the one typing `c`, with the same
+    /// Shift state
+    fn neighbor(&mut self, c: char) -> Option<char> {
+        let stroke = self.layout.key_stroke(c)?;
+        let (row, x) = key_position(stroke.code)?;
+        let neighbors = KEY_ROWS
+            .iter()
+            .enumerate()
+            .flat_map(|(r, codes)| codes.iter().map(move |code| (r, *code)))
+            .filter(|(r, code)| {
+                let (_, other) = key_position(code).unwrap();
+                match r.abs_diff(row) {
+                    0 => (other - x).abs() == 1.0,
+                    1 => (other - x).abs() <= 1.0,
+                    _ => false,
+                }
+            })
+            .filter_map(|(_, code)| self.layout.key_for_code(code, stroke.shift))
+            .filter_map(|n| n.key.chars().next())
+            .filter(|n| n.is_alphanumeric())
+            .collect::<Vec<_>>();
+        (!neighbors.is_empty()).then(|| neighbors[self.rng.gen_range(0..neighbors.len())])
+    }
+
+    fn press(&mut self, key: TypingKey) {
+        let code = match key {
+            TypingKey::Char(c) => self.layout.key_stroke(c).map(|stroke| stroke.code),
+            TypingKey::Backspace => Some("Backspace"),
+        };
+        let factor = digraph_factor(self.previous, code);
+        let interval = self.key_interval * factor * (0.2 * gauss(&mut self.rng)).exp();
+        // keys go down one after another, fast typists hold them shorter
+        let dwell = (self.style.dwell.as_secs_f64() * 1000.0 * (0.2 * gauss(&mut self.rng)).exp())
+            .min(interval * 0.6);
+        let delay = (interval - self.previous_dwell).max(0.0);
+
+        self.steps.push(TypingStep {
+            key,
+            delay: Duration::from_secs_f64(delay / 1000.0),
+            dwell: Duration::from_secs_f64(dwell / 1000.0),
+        });
+        self.previous = code;
+        self.previous_dwell = dwell;
+        self.burst_left -= 1;
+    }
+
+    /// Pauses between bursts at the word boundary before the next key
+    fn maybe_pause(&mut self) {
+        if self.burst_left > 0 {
+            return;
+        }
+        let pause = self.style.burst_pause.as_secs_f64() * 1000.0;
+        let noise = (0.35 * gauss(&mut self.rng)).exp();
+        self.pause(pause * noise);
+        self.burst_left = self.burst_length();
+    }
+
+    /// Adds `ms` to the delay of the next key press
+    fn pause(&mut self, ms: f64) {
+        self.previous_dwell -= ms;
+    }
+
+    fn burst_length(&mut self) -> i64 {
+        let mean = self.style.burst_keys.max(1) as f64;
+        self.rng.gen_range(mean * 0.5..=mean * 1.5).round() as i64
+    }
+}
+
+/// The row and horizontal position of the physical key `code`
+fn key_position(code: &str) -> Option<(usize, f64)> {
+    KEY_ROWS.iter().enumerate().find_map(|(row, codes)| {
+        let i = codes.iter().position(|c| *c == code)? as i32 + ROW_START[row];
+        Some((row, i as f64 + ROW_STAGGER[row]))
+    })
+}
+
+/// The finger typing `code` on a touch typist's keyboard, 0-3 are the left
+/// hand's pinky to index, 4-7 the right hand's index to pinky
+fn finger(code: &str) -> Option<u8> {
+    if code == "Backspace" {
+        return Some(7);
+    }
+    let column = KEY_ROWS.iter().enumerate().find_map(|(row, codes)| {
+        Some(codes.iter().position(|c| *c == code)? as i32 + ROW_START[row])
+    })?;
+    Some(match column {
+        i32::MIN..=0 => 0,
+        1 => 1,
+        2 => 2,
+        3 | 4 => 3,
+        5 | 6 => 4,
+        7 => 5,
+        8 => 6,
+        _ => 7,
+    })
+}
+
+/// How much longer than average the interval between two keys takes:
+/// alternating hands is quick, the same finger on another key slow
+fn digraph_factor(previous: Option<&str>, next: Option<&str>) -> f64 {
+    let (Some(previous), Some(next)) = (previous, next) else {
+        return 1.0;
+    };
+    match (finger(previous), finger(next)) {
+        _ if previous == next => 0.85,
+        (Some(a), Some(b)) if a == b => 1.4,
+        (Some(a), Some(b)) if (a < 4) != (b < 4) => 0.75,
+        (Some(_), Some(_)) => 1.0,
+        // the thumbs on the space bar work with either hand
+        _ => 0.9,
+    }
+}
+
+/// A standard normal sample, the Irwin-Hall approximation
+fn gauss(rng: &mut impl Rng) -> f64 {
+    (0..12).map(|_| rng.gen::<f64>()).sum::<f64>() - 6.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The text a plan leaves in an input
+    fn replay(steps: &[TypingStep]) -> String {
+        let mut text = String::new();
+        for step in steps {
+            match step.key {
+                TypingKey::Char(c) => text.push(c),
+                TypingKey::Backspace => {
+                    text.pop();
+                }
+            }
+        }
+        text
+    }
+
+    fn total(steps: &[TypingStep]) -> Duration {
+        steps.iter().map(|s| s.delay).sum()
+    }
+
+    #[test]
+    fn typos_get_corrected() {
+        let text = "The quick brown fox jumps over 13 lazy dogs";
+        let clean = TypingStyle::default()
+            .without_typos()
+            .plan(text, KeyboardLayout::Us);
+        assert_eq!(clean.len(), text.chars().count());
+        assert_eq!(replay(&clean), text);
+
+        let sloppy = TypingStyle {
+            typo_rate: 0.5,
+            ..Default::default()
+        };
+        for layout in [
+            KeyboardLayout::Us,
+            KeyboardLayout::German,
+            KeyboardLayout::French,
+        ] {
+            let steps = sloppy.plan(text, layout);
+            assert!(steps.iter().any(|s| s.key == TypingKey::Backspace));
+            assert_eq!(replay(&steps), text, "{layout:?}");
+        }
+    }
+
+    #[test]
+    fn neighbors_and_digraphs_follow_the_keyboard() {
+        let style = TypingStyle::default();
+        let mut planner = Planner {
+            style: &style,
+            layout: KeyboardLayout::Us,
+            rng: rand::thread_rng(),
+            key_interval: 0.0,
+            previous: None,
+            previous_dwell: 0.0,
+            burst_left: 0,
+            steps: Vec::new(),
+        };
+        for _ in 0..50 {
+            assert!("wedxza".contains(planner.neighbor('s').unwrap()));
+            assert!("QWSZ".contains(planner.neighbor('A').unwrap()));
+        }
+
+        // `fj` alternates hands, `ed` repeats the left middle finger
+        assert!(
+            digraph_factor(Some("KeyF"), Some("KeyJ")) < digraph_factor(Some("KeyE"), Some("KeyD"))
+        );
+
+        let slow = TypingStyle {
+            wpm: 20.0,
+            wpm_variance: 0.0,
+            ..style.without_typos()
+        };
+        let fast = TypingStyle {
+            wpm: 100.0,
+            ..slow.clone()
+        };
+        let text = "a sentence of some length";
+        assert!(
+            total(&slow.plan(text, KeyboardLayout::Us))
+                > total(&fast.plan(text, KeyboardLayout::Us)) * 2
+        );
+
+        // keys don't overlap, so high speeds come from shorter hold times
+        let fastest = TypingStyle {
+            wpm: 150.0,
+            burst_keys: 1000,
+            ..slow
+        };
+        let steps = fastest.plan(text, KeyboardLayout::Us);
+        let elapsed = steps.iter().map(|s| s.delay + s.dwell).sum::<Duration>();
+        let wpm = steps.len() as f64 / 5.0 / (elapsed.as_secs_f64() / 60.0);
+        assert!(wpm > 120.0, "{wpm}");
+    }
+}